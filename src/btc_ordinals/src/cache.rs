@@ -0,0 +1,472 @@
+use candid::{CandidType, Deserialize};
+
+use ic_stable_structures::{storable::Bound, StableBTreeMap, StableCell, Storable};
+
+use crate::config;
+use crate::memory::{Memory, get_memory, CACHE_MEMORY_ID, CACHE_STATS_MEMORY_ID, CACHE_EXPIRY_MEMORY_ID};
+use crate::types::{Provider, EndPoint, OrdFunction, Response, CacheStats};
+use crate::utils::normalize_function;
+
+use std::{borrow::Cow, cell::RefCell};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Maximum number of cached responses, and of bytes used by them.
+/// Once reached, the entries closest to their expiry are evicted first, the immutable ones last.
+const MAX_CACHE_ENTRIES: u64 = 100_000;
+const MAX_CACHE_BYTES: u64 = 1 << 30;
+
+/// Maximum number of entries removed by a single call, so that the instruction limit is never
+/// reached whatever the size of the cache.
+const MAX_EXPIRED_PER_INSERT: usize = 100;
+const MAX_EVICTED_PER_INSERT: usize = 100;
+pub const MAX_INVALIDATED_PER_CALL: usize = 1_000;
+
+const PROVIDERS: [Provider; 3] = [Provider::Hiro, Provider::Bitgem, Provider::Ord];
+
+/// How long the response of an end point can be served from the cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CachePolicy {
+    // The data never changes once confirmed
+    Immutable,
+    // The data can change, the cached response is considered fresh for the given duration
+    Ttl{ seconds: u64 },
+    // The data changes too often to be cached
+    Never,
+}
+
+pub fn cache_policy(end_point: EndPoint) -> CachePolicy {
+    match end_point {
+        EndPoint::SatInfo                  => CachePolicy::Immutable,
+        EndPoint::InscriptionContent       => CachePolicy::Immutable,
+        EndPoint::SatRange                 => CachePolicy::Ttl{ seconds: 3_600 }, // The ranges are only known once the output is confirmed
+        EndPoint::SatInscriptions          => CachePolicy::Ttl{ seconds: 600 },   // New inscriptions can be made on the sat
+        EndPoint::AddressInscriptions      => CachePolicy::Ttl{ seconds: 600 },   // The inscriptions move in and out on transfer
+        EndPoint::SearchInscriptions       => CachePolicy::Ttl{ seconds: 600 },   // New inscriptions can match the filters
+        EndPoint::InscriptionInfo          => CachePolicy::Ttl{ seconds: 600 },   // The location and address change on transfer
+        EndPoint::InscriptionTransfers     => CachePolicy::Ttl{ seconds: 600 },   // Every transfer adds a location
+        EndPoint::Brc20Details             => CachePolicy::Ttl{ seconds: 600 },   // The minted supply and holders change on mint
+        EndPoint::Brc20Holders             => CachePolicy::Never,
+        EndPoint::Brc20Balances            => CachePolicy::Ttl{ seconds: 600 },   // The balances change on mint and transfer
+        EndPoint::Brc20Activity            => CachePolicy::Ttl{ seconds: 600 },   // New operations can match the filters
+        EndPoint::Brc20Tokens              => CachePolicy::Ttl{ seconds: 600 },   // New tokens get deployed, the tx counts change on every operation
+        EndPoint::RuneDetails              => CachePolicy::Ttl{ seconds: 600 },   // The supply changes on mint and burn
+        EndPoint::RuneHolders              => CachePolicy::Never,
+        EndPoint::RuneBalances             => CachePolicy::Ttl{ seconds: 600 },   // The balances change on every operation
+        EndPoint::RuneActivity             => CachePolicy::Ttl{ seconds: 600 },   // New operations can match the filters
+        EndPoint::BlockInscriptionActivity => CachePolicy::Ttl{ seconds: 3_600 }, // Its transfers part, which only a reorg can change
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CacheKey {
+    pub provider: Provider,
+    pub end_point: EndPoint,
+    // Candid encoding of the normalized function
+    pub args: Vec<u8>,
+    // The configured base URL of the provider, if any, so that the responses of another server
+    // are never served once it changed. The keys stored before it was added decode to None.
+    pub base_url: Option<String>,
+}
+
+impl CacheKey {
+    pub fn new(provider: Provider, end_point: EndPoint, function: OrdFunction) -> Self {
+        Self { provider, end_point, args: encode_args(function), base_url: config::base_url(provider) }
+    }
+}
+
+fn encode_args(function: OrdFunction) -> Vec<u8> {
+    candid::encode_one(normalize_function(function))
+        .expect("Failed to encode the cache key arguments")
+}
+
+impl Storable for CacheKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode the cache key"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("Failed to decode the cache key")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CacheStats {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode the cache stats"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("Failed to decode the cache stats")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+// The entries are stored as raw candid bytes and only decoded on read, so that an entry which
+// cannot be decoded anymore (e.g. because the response type changed in an upgrade) is treated
+// as a cache miss instead of trapping.
+#[derive(Clone, Debug, CandidType, Deserialize)]
+struct CacheEntry {
+    response: Response,
+    // Time of insertion, in nanoseconds since the epoch
+    timestamp: u64,
+}
+
+// Index of the entries by expiry time, used to sweep the expired entries and to pick the
+// entries to evict without scanning the whole cache.
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+struct ExpiryKey {
+    // In nanoseconds since the epoch, u64::MAX for the immutable entries
+    expires_at: u64,
+    key: CacheKey,
+}
+
+impl Storable for ExpiryKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode the cache expiry key"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("Failed to decode the cache expiry key")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+thread_local! {
+    static CACHE: RefCell<StableBTreeMap<CacheKey, Vec<u8>, Memory>> =
+        RefCell::new(StableBTreeMap::init(get_memory(CACHE_MEMORY_ID)));
+
+    static EXPIRY: RefCell<StableBTreeMap<ExpiryKey, (), Memory>> =
+        RefCell::new(StableBTreeMap::init(get_memory(CACHE_EXPIRY_MEMORY_ID)));
+
+    static STATS: RefCell<StableCell<CacheStats, Memory>> =
+        RefCell::new(StableCell::init(get_memory(CACHE_STATS_MEMORY_ID), CacheStats::default())
+            .expect("Failed to initialize the cache stats"));
+}
+
+/// Returns the cached response if it is still fresh at the given time (in nanoseconds).
+pub fn get(key: &CacheKey, now: u64) -> Option<Response> {
+    let policy = cache_policy(key.end_point);
+    if policy == CachePolicy::Never {
+        return None;
+    }
+    let response = CACHE.with(|cache| cache.borrow().get(key))
+        .and_then(|bytes| match candid::decode_one::<CacheEntry>(&bytes) {
+            Ok(entry) if is_fresh(&policy, entry.timestamp, now) => Some(entry.response),
+            // Evict the expired and undecodable entries
+            _ => {
+                remove(key);
+                update_stats(|stats| stats.evictions += 1);
+                None
+            },
+        });
+    match response {
+        Some(_) => update_stats(|stats| stats.hits += 1),
+        None => update_stats(|stats| stats.misses += 1),
+    };
+    response
+}
+
+/// Caches the response at the given time (in nanoseconds), if the end point policy allows it.
+/// A batch of expired entries is swept first, then the entries closest to their expiry are
+/// evicted if the cache is full. The response is not cached if there is still no room for it.
+pub fn insert(key: CacheKey, response: Response, now: u64) {
+    insert_with_limits(key, response, now, MAX_CACHE_ENTRIES, MAX_CACHE_BYTES);
+}
+
+fn insert_with_limits(key: CacheKey, response: Response, now: u64, max_entries: u64, max_bytes: u64) {
+    let policy = cache_policy(key.end_point);
+    let Some(expires_at) = expires_at(&policy, now) else {
+        return;
+    };
+    if let Ok(bytes) = candid::encode_one(CacheEntry { response, timestamp: now }) {
+        remove(&key);
+        sweep_expired(now);
+        let size = entry_size(&key, &bytes);
+        if !make_room(size, max_entries, max_bytes) {
+            return;
+        }
+        EXPIRY.with(|expiry| expiry.borrow_mut().insert(ExpiryKey{ expires_at, key: key.clone() }, ()));
+        CACHE.with(|cache| cache.borrow_mut().insert(key, bytes));
+        update_stats(|stats| stats.bytes_used += size);
+    }
+}
+
+/// Removes the cached responses of the end point, for every provider. If a function is
+/// given, only the responses to this function from the current base URLs are removed. Returns the number of removed entries.
+/// At most MAX_INVALIDATED_PER_CALL entries are removed per call, call it again until it returns 0.
+pub fn invalidate(end_point: EndPoint, function: Option<OrdFunction>) -> u64 {
+    match function.map(encode_args) {
+        Some(args) => PROVIDERS.iter()
+            .filter(|provider| remove(&CacheKey{ provider: **provider, end_point, args: args.clone(), base_url: config::base_url(**provider) }))
+            .count() as u64,
+        None => {
+            // The keys are ordered by provider then end point, so the entries of an end point are contiguous
+            let keys : Vec<CacheKey> = CACHE.with(|cache| {
+                let cache = cache.borrow();
+                PROVIDERS.iter()
+                    .flat_map(|provider| cache
+                        .range(CacheKey{ provider: *provider, end_point, args: vec![], base_url: None }..)
+                        .map(|(key, _)| key)
+                        .take_while(|key| key.provider == *provider && key.end_point == end_point)
+                        .take(MAX_INVALIDATED_PER_CALL)
+                        .collect::<Vec<_>>())
+                    .take(MAX_INVALIDATED_PER_CALL)
+                    .collect()
+            });
+            keys.iter().for_each(|key| { remove(key); });
+            keys.len() as u64
+        },
+    }
+}
+
+pub fn stats() -> CacheStats {
+    STATS.with(|stats| stats.borrow().get().clone())
+}
+
+// Removes the entry and its expiry index, returns whether it was cached
+fn remove(key: &CacheKey) -> bool {
+    let Some(bytes) = CACHE.with(|cache| cache.borrow_mut().remove(key)) else {
+        return false;
+    };
+    // An undecodable entry leaves its index behind, which is dropped once it expires
+    if let Ok(entry) = candid::decode_one::<CacheEntry>(&bytes) {
+        if let Some(expires_at) = expires_at(&cache_policy(key.end_point), entry.timestamp) {
+            EXPIRY.with(|expiry| expiry.borrow_mut().remove(&ExpiryKey{ expires_at, key: key.clone() }));
+        }
+    }
+    let size = entry_size(key, &bytes);
+    update_stats(|stats| stats.bytes_used = stats.bytes_used.saturating_sub(size));
+    true
+}
+
+// Removes a batch of the entries expired at the given time
+fn sweep_expired(now: u64) {
+    let expired : Vec<ExpiryKey> = EXPIRY.with(|expiry| {
+        expiry.borrow()
+            .iter()
+            .map(|(expiry_key, _)| expiry_key)
+            .take_while(|expiry_key| expiry_key.expires_at <= now)
+            .take(MAX_EXPIRED_PER_INSERT)
+            .collect()
+    });
+    expired.into_iter().for_each(|expiry_key| {
+        EXPIRY.with(|expiry| expiry.borrow_mut().remove(&expiry_key));
+        if remove(&expiry_key.key) {
+            update_stats(|stats| stats.evictions += 1);
+        }
+    });
+}
+
+// Evicts the entries closest to their expiry until an entry of the given size fits,
+// returns whether it fits
+fn make_room(size: u64, max_entries: u64, max_bytes: u64) -> bool {
+    if size > max_bytes {
+        return false;
+    }
+    for _ in 0..MAX_EVICTED_PER_INSERT {
+        let entries = CACHE.with(|cache| cache.borrow().len());
+        if entries < max_entries && stats().bytes_used + size <= max_bytes {
+            return true;
+        }
+        let Some((expiry_key, _)) = EXPIRY.with(|expiry| expiry.borrow().first_key_value()) else {
+            return false;
+        };
+        EXPIRY.with(|expiry| expiry.borrow_mut().remove(&expiry_key));
+        if remove(&expiry_key.key) {
+            update_stats(|stats| stats.evictions += 1);
+        }
+    }
+    false
+}
+
+fn expires_at(policy: &CachePolicy, timestamp: u64) -> Option<u64> {
+    match policy {
+        CachePolicy::Immutable => Some(u64::MAX),
+        CachePolicy::Ttl{ seconds } => Some(timestamp.saturating_add(seconds.saturating_mul(NANOS_PER_SEC))),
+        CachePolicy::Never => None,
+    }
+}
+
+fn is_fresh(policy: &CachePolicy, timestamp: u64, now: u64) -> bool {
+    match policy {
+        CachePolicy::Immutable => true,
+        CachePolicy::Ttl{ seconds } => now.saturating_sub(timestamp) < seconds.saturating_mul(NANOS_PER_SEC),
+        CachePolicy::Never => false,
+    }
+}
+
+fn entry_size(key: &CacheKey, bytes: &[u8]) -> u64 {
+    (key.to_bytes().len() + bytes.len()) as u64
+}
+
+fn update_stats(update: impl FnOnce(&mut CacheStats)) {
+    STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        let mut value = stats.get().clone();
+        update(&mut value);
+        stats.set(value).expect("Failed to update the cache stats");
+    });
+}
+
+#[test]
+fn test_get_insert() {
+
+    use crate::types::{SatInfoArgs, SatInfo, SatoshiRarity, Satribute, InscriptionContentArgs, SatNotation};
+
+    let function = OrdFunction::SatInfo(SatInfoArgs{ sat: SatNotation::Integer(85000000000) });
+    let response = Response::SatInfo(SatInfo {
+        height: 17,
+        cycle: 0,
+        epoch: 0,
+        period: 0,
+        rarity: SatoshiRarity::Uncommon,
+        satributes: vec![Satribute::Uncommon, Satribute::Vintage, Satribute::Alpha],
+    });
+
+    let key = CacheKey::new(Provider::Hiro, EndPoint::SatInfo, function.clone());
+    assert_eq!(get(&key, 0), None);
+    insert(key.clone(), response.clone(), 0);
+    assert_eq!(get(&key, u64::MAX), Some(response));
+
+    // The entries are per provider
+    assert_eq!(get(&CacheKey::new(Provider::Bitgem, EndPoint::SatInfo, function), 0), None);
+
+    // Equivalent arguments share the same entry
+    let content = Response::InscriptionContent("whatever".as_bytes().to_vec());
+    insert(CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, OrdFunction::InscriptionContent(
+        InscriptionContentArgs{ inscription_id: "38C46A8BF7EC90BC7F6B797E7DC84BAA97F4E5FD4286B92FE1B50176D03B18DCi0".to_string() }
+    )), content.clone(), 0);
+    assert_eq!(get(&CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, OrdFunction::InscriptionContent(
+        InscriptionContentArgs{ inscription_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string() }
+    )), 0), Some(content));
+
+    let stats = stats();
+    assert_eq!(stats.hits, 2);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.evictions, 0);
+    assert!(stats.bytes_used > 0);
+}
+
+#[test]
+fn test_policies() {
+
+    use crate::types::{Brc20DetailsArgs, Brc20HoldersArgs, HiroBrc20Holders, HiroBrc20Details, HiroBrc20Token, HiroBrc20Supply, Brc20Amount};
+
+    // Never cached
+    let key = CacheKey::new(Provider::Hiro, EndPoint::Brc20Holders, OrdFunction::Brc20Holders(
+        Brc20HoldersArgs{ ticker: "ordi".to_string(), offset: 0, limit: 1 }
+    ));
+    insert(key.clone(), Response::Brc20Holders(HiroBrc20Holders{ limit: 1, offset: 0, total: 0, results: vec![] }), 0);
+    assert_eq!(get(&key, 0), None);
+    assert_eq!(stats().bytes_used, 0);
+
+    // Cached for 10 minutes
+    let key = CacheKey::new(Provider::Hiro, EndPoint::Brc20Details, OrdFunction::Brc20Details(
+        Brc20DetailsArgs{ ticker: "ordi".to_string() }
+    ));
+    let response = Response::Brc20Details(HiroBrc20Details {
+        token: HiroBrc20Token {
+            id: "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0".to_string(),
+            number: 348020,
+            block_height: 779832,
+            tx_id: "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735".to_string(),
+            address: "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06".to_string(),
+            ticker: "ordi".to_string(),
+            max_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+            mint_limit: Brc20Amount("1000000000000000000000".parse().unwrap()),
+            decimals: 18,
+            deploy_timestamp: 1678248991000,
+            minted_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+            tx_count: 225407,
+        },
+        supply: HiroBrc20Supply {
+            max_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+            minted_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+            holders: 15120,
+        },
+    });
+    insert(key.clone(), response.clone(), 0);
+    assert_eq!(get(&key, 599 * NANOS_PER_SEC), Some(response));
+    assert_eq!(get(&key, 600 * NANOS_PER_SEC), None);
+    assert_eq!(stats().evictions, 1);
+    assert_eq!(stats().bytes_used, 0);
+}
+
+#[test]
+fn test_invalidate() {
+
+    use crate::types::{InscriptionContentArgs};
+
+    let function = |inscription_id: &str| OrdFunction::InscriptionContent(InscriptionContentArgs{ inscription_id: inscription_id.to_string() });
+    let content = Response::InscriptionContent("whatever".as_bytes().to_vec());
+
+    insert(CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, function("a")), content.clone(), 0);
+    insert(CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, function("b")), content.clone(), 0);
+    insert(CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, function("c")), content.clone(), 0);
+
+    assert_eq!(invalidate(EndPoint::SatInfo, None), 0);
+    assert_eq!(invalidate(EndPoint::InscriptionContent, Some(function("A"))), 1);
+    assert_eq!(get(&CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, function("a")), 0), None);
+    assert_eq!(get(&CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, function("b")), 0), Some(content));
+    assert_eq!(invalidate(EndPoint::InscriptionContent, None), 2);
+    assert_eq!(stats().bytes_used, 0);
+}
+
+#[test]
+fn test_sweep_and_eviction() {
+
+    use crate::types::{InscriptionContentArgs, InscriptionInfoArgs};
+
+    let content = |inscription_id: &str| CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, OrdFunction::InscriptionContent(
+        InscriptionContentArgs{ inscription_id: inscription_id.to_string() }
+    ));
+    let info = |inscription_id: &str| CacheKey::new(Provider::Hiro, EndPoint::InscriptionInfo, OrdFunction::InscriptionInfo(
+        InscriptionInfoArgs{ inscription_id: inscription_id.to_string() }
+    ));
+    let response = Response::InscriptionContent("whatever".as_bytes().to_vec());
+
+    // The expired entries are swept on insert, without being read again
+    insert(info("a"), response.clone(), 0);
+    insert(info("b"), response.clone(), 600 * NANOS_PER_SEC);
+    assert_eq!(CACHE.with(|cache| cache.borrow().len()), 1);
+    assert_eq!(stats().evictions, 1);
+
+    // Once full, the entries closest to their expiry are evicted first, the immutable ones last
+    insert_with_limits(content("c"), response.clone(), 600 * NANOS_PER_SEC, 2, u64::MAX);
+    insert_with_limits(info("d"), response.clone(), 700 * NANOS_PER_SEC, 2, u64::MAX);
+    assert_eq!(get(&info("b"), 700 * NANOS_PER_SEC), None);
+    assert_eq!(get(&content("c"), 700 * NANOS_PER_SEC), Some(response.clone()));
+    assert_eq!(get(&info("d"), 700 * NANOS_PER_SEC), Some(response.clone()));
+    assert_eq!(stats().evictions, 2);
+
+    // A response which cannot fit is not cached
+    insert_with_limits(info("e"), response.clone(), 700 * NANOS_PER_SEC, 10, 1);
+    assert_eq!(get(&info("e"), 700 * NANOS_PER_SEC), None);
+    assert_eq!(EXPIRY.with(|expiry| expiry.borrow().len()), 2);
+}
+
+#[test]
+fn test_base_url() {
+
+    use crate::types::{InscriptionContentArgs, ProviderConfig};
+
+    let key = || CacheKey::new(Provider::Hiro, EndPoint::InscriptionContent, OrdFunction::InscriptionContent(
+        InscriptionContentArgs{ inscription_id: "a".to_string() }
+    ));
+    let content = Response::InscriptionContent("whatever".as_bytes().to_vec());
+    insert(key(), content.clone(), 0);
+    assert_eq!(get(&key(), 0), Some(content.clone()));
+
+    // The responses of the previous server are not served anymore
+    config::set_provider_config(ProviderConfig{ provider: Provider::Hiro, base_url: Some("https://hiro.example.com".to_string()), headers: None }).unwrap();
+    assert_eq!(get(&key(), 0), None);
+    insert(key(), content.clone(), 0);
+    assert_eq!(get(&key(), 0), Some(content));
+
+    // Both are removed by the invalidation of the end point
+    assert_eq!(invalidate(EndPoint::InscriptionContent, None), 2);
+}
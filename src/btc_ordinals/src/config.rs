@@ -0,0 +1,184 @@
+use candid::{CandidType, Deserialize};
+
+use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+use ic_stable_structures::{storable::Bound, StableCell, Storable};
+
+use crate::cycles::DEFAULT_SUBNET_SIZE;
+use crate::memory::{Memory, get_memory, CONFIG_MEMORY_ID};
+use crate::types::{InitArgs, OrdError, Provider, ProviderConfig};
+
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap};
+
+/// The canister settings, set at install and upgrade time.
+/// Warning: the fields added after the first release shall be optional, so that the
+/// config stored by a previous version can still be decoded after an upgrade.
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq, Eq)]
+pub struct Config {
+    // Number of nodes of the subnet the canister runs on, used to compute the cycles costs
+    pub subnet_size: u32,
+    // Base URLs overriding the default ones of the providers
+    pub base_urls: Option<BTreeMap<Provider, String>>,
+    // Headers attached to every outcall of the providers, e.g. API keys.
+    // Warning: they are secret, hence shall never be returned by any method.
+    pub headers: Option<BTreeMap<Provider, Vec<HttpHeader>>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { subnet_size: DEFAULT_SUBNET_SIZE, base_urls: None, headers: None }
+    }
+}
+
+impl Storable for Config {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode the config"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("Failed to decode the config")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+thread_local! {
+    static CONFIG: RefCell<StableCell<Config, Memory>> =
+        RefCell::new(StableCell::init(get_memory(CONFIG_MEMORY_ID), Config::default())
+            .expect("Failed to initialize the config"));
+}
+
+/// Applies the given init or upgrade arguments. The settings left empty keep their
+/// current value, which is the default one on install.
+/// Traps if the arguments are invalid, so that the install or upgrade is rejected.
+pub fn apply(args: Option<InitArgs>) {
+    let args = match args {
+        Some(args) => args,
+        None => return,
+    };
+    if let Some(subnet_size) = args.subnet_size {
+        if subnet_size == 0 {
+            ic_cdk::trap("The subnet size shall be strictly positive");
+        }
+        update(|config| config.subnet_size = subnet_size);
+    }
+    for provider_config in args.providers.unwrap_or_default() {
+        if let Err(err) = set_provider_config(provider_config) {
+            ic_cdk::trap(&format!("Invalid provider config: {:?}", err));
+        }
+    }
+}
+
+/// Updates the settings of the given provider, fails without updating anything if
+/// one of them is invalid.
+pub fn set_provider_config(provider_config: ProviderConfig) -> Result<(), OrdError> {
+    let base_url = provider_config.base_url.map(|url| validate_base_url(&url)).transpose()?;
+    let headers = provider_config.headers.map(validate_headers).transpose()?;
+    update(|config| {
+        if let Some(base_url) = base_url {
+            config.base_urls.get_or_insert_with(BTreeMap::new).insert(provider_config.provider, base_url);
+        }
+        if let Some(headers) = headers {
+            config.headers.get_or_insert_with(BTreeMap::new).insert(provider_config.provider, headers);
+        }
+    });
+    Ok(())
+}
+
+pub fn subnet_size() -> u32 {
+    CONFIG.with(|config| config.borrow().get().subnet_size)
+}
+
+/// The configured base URL of the provider, if any.
+pub fn base_url(provider: Provider) -> Option<String> {
+    CONFIG.with(|config| config.borrow().get().base_urls.as_ref().and_then(|urls| urls.get(&provider).cloned()))
+}
+
+/// The configured headers of the provider, empty if none.
+pub fn headers(provider: Provider) -> Vec<HttpHeader> {
+    CONFIG.with(|config| config.borrow().get().headers.as_ref().and_then(|headers| headers.get(&provider).cloned()).unwrap_or_default())
+}
+
+// The header names are trimmed, and shall be valid HTTP tokens. The values are trimmed too,
+// they cannot contain line breaks.
+fn validate_headers(headers: Vec<HttpHeader>) -> Result<Vec<HttpHeader>, OrdError> {
+    headers.into_iter().map(|header| {
+        let name = header.name.trim();
+        let value = header.value.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)) {
+            return Err(OrdError::InvalidConfigError(format!("Invalid header name: {}", name)));
+        }
+        // The value is not part of the error, because it is likely to be secret.
+        if value.contains(['\r', '\n']) {
+            return Err(OrdError::InvalidConfigError(format!("Invalid value for header: {}", name)));
+        }
+        Ok(HttpHeader{ name: name.to_string(), value: value.to_string() })
+    }).collect()
+}
+
+// The HTTP outcalls only support HTTPS. The trailing slashes are removed, because the
+// services append paths starting with a slash.
+fn validate_base_url(url: &str) -> Result<String, OrdError> {
+    let url = url.trim().trim_end_matches('/');
+    match url.strip_prefix("https://") {
+        Some(host) if !host.is_empty() && !host.contains(char::is_whitespace) => Ok(url.to_string()),
+        _ => Err(OrdError::InvalidConfigError(format!("Invalid base URL: {}, expected https://<host>", url))),
+    }
+}
+
+fn update(f: impl FnOnce(&mut Config)) {
+    CONFIG.with(|config| {
+        let mut config = config.borrow_mut();
+        let mut updated = config.get().clone();
+        f(&mut updated);
+        config.set(updated).expect("Failed to update the config");
+    });
+}
+
+#[test]
+fn test_validate_base_url() {
+    assert_eq!(validate_base_url("https://bitgem.example.com"), Ok("https://bitgem.example.com".to_string()));
+    assert_eq!(validate_base_url(" https://bitgem.example.com:8080/api/ "), Ok("https://bitgem.example.com:8080/api".to_string()));
+    assert!(validate_base_url("http://bitgem.example.com").is_err());
+    assert!(validate_base_url("https://").is_err());
+    assert!(validate_base_url("https://bitgem example.com").is_err());
+}
+
+#[test]
+fn test_set_provider_config() {
+    assert_eq!(base_url(Provider::Bitgem), None);
+
+    set_provider_config(ProviderConfig{ provider: Provider::Bitgem, base_url: Some("https://bitgem.example.com/".to_string()), headers: None }).unwrap();
+    assert_eq!(base_url(Provider::Bitgem), Some("https://bitgem.example.com".to_string()));
+    assert_eq!(base_url(Provider::Hiro), None);
+
+    // An invalid URL does not override the current one
+    assert!(set_provider_config(ProviderConfig{ provider: Provider::Bitgem, base_url: Some("ftp://bitgem.example.com".to_string()), headers: None }).is_err());
+    // Neither does an empty one
+    set_provider_config(ProviderConfig{ provider: Provider::Bitgem, base_url: None, headers: None }).unwrap();
+    assert_eq!(base_url(Provider::Bitgem), Some("https://bitgem.example.com".to_string()));
+}
+
+#[test]
+fn test_set_provider_headers() {
+    let api_key = || HttpHeader{ name: " x-hiro-api-key".to_string(), value: "secret ".to_string() };
+    assert_eq!(headers(Provider::Hiro), vec![]);
+
+    set_provider_config(ProviderConfig{ provider: Provider::Hiro, base_url: None, headers: Some(vec![api_key()]) }).unwrap();
+    assert_eq!(headers(Provider::Hiro), vec![HttpHeader{ name: "x-hiro-api-key".to_string(), value: "secret".to_string() }]);
+    assert_eq!(headers(Provider::Bitgem), vec![]);
+    // The base URL is left unchanged
+    assert_eq!(base_url(Provider::Hiro), None);
+
+    // Invalid headers are rejected
+    let invalid_name = HttpHeader{ name: "x-hiro api-key".to_string(), value: "secret".to_string() };
+    let invalid_value = HttpHeader{ name: "x-hiro-api-key".to_string(), value: "secret\r\nHost: evil.com".to_string() };
+    for invalid in [invalid_name, invalid_value] {
+        assert!(set_provider_config(ProviderConfig{ provider: Provider::Hiro, base_url: None, headers: Some(vec![invalid]) }).is_err());
+        assert_eq!(headers(Provider::Hiro).len(), 1);
+    }
+
+    // An empty list removes the headers
+    set_provider_config(ProviderConfig{ provider: Provider::Hiro, base_url: None, headers: Some(vec![]) }).unwrap();
+    assert_eq!(headers(Provider::Hiro), vec![]);
+}
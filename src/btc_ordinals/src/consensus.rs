@@ -0,0 +1,272 @@
+use crate::types::{ConsensusStrategy, MultiOrdResult, ProviderOrdResult, ProviderOrdError, Response};
+
+/// Reduces the results of the different providers into a single result according to the strategy.
+/// The results are expected to be in the providers order, which is used to break ties.
+/// If errors are ignored, the failing providers are left out of the comparison and reported
+/// apart, unless every provider failed.
+pub fn reduce_results(strategy: ConsensusStrategy, ignore_errors: bool, results: Vec<ProviderOrdResult>) -> MultiOrdResult {
+
+    let (successes, failures) : (Vec<_>, Vec<_>) = results.iter().cloned().partition(|other| other.result.is_ok());
+
+    if !ignore_errors || successes.is_empty() || failures.is_empty() {
+        return reduce(strategy, results);
+    }
+
+    let failed = failures
+        .into_iter()
+        .filter_map(|other| other.result.err().map(|error| ProviderOrdError{ provider: other.provider, error }))
+        .collect();
+    let providers = successes.iter().map(|other| other.provider).collect();
+
+    match reduce(strategy, successes) {
+        MultiOrdResult::Consistent(Ok(response)) => MultiOrdResult::Consensus {
+            result: response,
+            agreed: providers,
+            dissented: vec![],
+            failed,
+        },
+        MultiOrdResult::Consensus{ result, agreed, dissented, .. } => MultiOrdResult::Consensus {
+            result,
+            agreed,
+            dissented,
+            failed,
+        },
+        _ => MultiOrdResult::Inconsistent(results),
+    }
+}
+
+fn reduce(strategy: ConsensusStrategy, results: Vec<ProviderOrdResult>) -> MultiOrdResult {
+
+    let first = match results.first() {
+        Some(first) => first.clone(),
+        // This should never happen, hence the panic.
+        None => panic!("No results"),
+    };
+
+    // All the providers agree, whatever the strategy.
+    if results.iter().all(|other| other.result == first.result) {
+        return MultiOrdResult::Consistent(first.result);
+    }
+
+    // Only successful responses can reach a consensus.
+    let consensus = match strategy {
+        ConsensusStrategy::Equality => None,
+        ConsensusStrategy::Threshold{ min } => largest_group(&results)
+            .filter(|(_, count)| *count >= min as usize)
+            .map(|(response, _)| response),
+        ConsensusStrategy::Majority => largest_group(&results)
+            .filter(|(_, count)| *count * 2 > results.len())
+            .map(|(response, _)| response),
+        ConsensusStrategy::FirstSuccess => results
+            .iter()
+            .find_map(|other| other.result.clone().ok()),
+    };
+
+    match consensus {
+        None => MultiOrdResult::Inconsistent(results),
+        Some(response) => {
+            let (agreed, dissented) : (Vec<_>, Vec<_>) = results
+                .into_iter()
+                .partition(|other| other.result.as_ref() == Ok(&response));
+            MultiOrdResult::Consensus {
+                result: response,
+                agreed: agreed.into_iter().map(|other| other.provider).collect(),
+                dissented,
+                failed: vec![],
+            }
+        }
+    }
+}
+
+// Returns the most common successful response with its number of occurrences.
+// In case of a tie, the response coming first in the providers order wins.
+fn largest_group(results: &[ProviderOrdResult]) -> Option<(Response, usize)> {
+    let mut groups : Vec<(Response, usize)> = vec![];
+    for response in results.iter().filter_map(|other| other.result.as_ref().ok()) {
+        match groups.iter_mut().find(|(group, _)| group == response) {
+            Some((_, count)) => *count += 1,
+            None => groups.push((response.clone(), 1)),
+        }
+    }
+    groups.into_iter().fold(None, |largest, group| match largest {
+        Some(largest) if largest.1 >= group.1 => Some(largest),
+        _ => Some(group),
+    })
+}
+
+#[cfg(test)]
+fn sat_info(height: u64) -> Response {
+    use crate::types::{SatInfo, SatoshiRarity};
+    Response::SatInfo(SatInfo { height, cycle: 0, epoch: 0, period: 0, rarity: SatoshiRarity::Common, satributes: vec![] })
+}
+
+#[cfg(test)]
+fn http_error() -> crate::types::OrdError {
+    use crate::types::{OrdError, HttpSendError};
+    use ic_cdk::api::call::RejectionCode;
+    OrdError::HttpSendError(HttpSendError{ rejection_code: RejectionCode::SysTransient })
+}
+
+#[test]
+fn test_unanimity() {
+
+    use crate::types::Provider;
+
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(17)) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Ok(sat_info(17)) },
+    ];
+    for strategy in [ConsensusStrategy::Equality, ConsensusStrategy::Threshold{ min: 2 }, ConsensusStrategy::Majority, ConsensusStrategy::FirstSuccess] {
+        assert_eq!(reduce_results(strategy, false, results.clone()), MultiOrdResult::Consistent(Ok(sat_info(17))));
+    }
+}
+
+#[test]
+fn test_one_provider_fails() {
+
+    use crate::types::Provider;
+
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Err(http_error()) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Ok(sat_info(17)) },
+    ];
+    let consensus = MultiOrdResult::Consensus {
+        result: sat_info(17),
+        agreed: vec![Provider::Bitgem],
+        dissented: vec![ProviderOrdResult{ provider: Provider::Hiro, result: Err(http_error()) }],
+        failed: vec![],
+    };
+    assert_eq!(reduce_results(ConsensusStrategy::Equality, false, results.clone()), MultiOrdResult::Inconsistent(results.clone()));
+    assert_eq!(reduce_results(ConsensusStrategy::Threshold{ min: 1 }, false, results.clone()), consensus);
+    assert_eq!(reduce_results(ConsensusStrategy::Threshold{ min: 2 }, false, results.clone()), MultiOrdResult::Inconsistent(results.clone()));
+    assert_eq!(reduce_results(ConsensusStrategy::Majority, false, results.clone()), MultiOrdResult::Inconsistent(results.clone()));
+    assert_eq!(reduce_results(ConsensusStrategy::FirstSuccess, false, results.clone()), consensus);
+}
+
+#[test]
+fn test_majority() {
+
+    use crate::types::Provider;
+
+    // The providers are duplicated for the sake of the test
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(18)) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Ok(sat_info(17)) },
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(17)) },
+    ];
+    assert_eq!(reduce_results(ConsensusStrategy::Majority, false, results.clone()), MultiOrdResult::Consensus {
+        result: sat_info(17),
+        agreed: vec![Provider::Bitgem, Provider::Hiro],
+        dissented: vec![ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(18)) }],
+        failed: vec![],
+    });
+    // The first success is not necessarily the most common one
+    assert_eq!(reduce_results(ConsensusStrategy::FirstSuccess, false, results.clone()), MultiOrdResult::Consensus {
+        result: sat_info(18),
+        agreed: vec![Provider::Hiro],
+        dissented: vec![
+            ProviderOrdResult{ provider: Provider::Bitgem, result: Ok(sat_info(17)) },
+            ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(17)) },
+        ],
+        failed: vec![],
+    });
+}
+
+#[test]
+fn test_all_providers_fail() {
+
+    use crate::types::{Provider, OrdError};
+
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Err(http_error()) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Err(OrdError::ResponseError("Not found".to_string())) },
+    ];
+    assert_eq!(reduce_results(ConsensusStrategy::FirstSuccess, false, results.clone()), MultiOrdResult::Inconsistent(results.clone()));
+    assert_eq!(reduce_results(ConsensusStrategy::Threshold{ min: 0 }, false, results.clone()), MultiOrdResult::Inconsistent(results));
+}
+
+#[test]
+fn test_ignore_errors() {
+
+    use crate::types::{Provider, OrdError};
+
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(17)) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Err(http_error()) },
+    ];
+    // The failing provider does not prevent the equality
+    assert_eq!(reduce_results(ConsensusStrategy::Equality, true, results.clone()), MultiOrdResult::Consensus {
+        result: sat_info(17),
+        agreed: vec![Provider::Hiro],
+        dissented: vec![],
+        failed: vec![ProviderOrdError{ provider: Provider::Bitgem, error: http_error() }],
+    });
+    // Nor the majority
+    assert_eq!(reduce_results(ConsensusStrategy::Majority, true, results.clone()), MultiOrdResult::Consensus {
+        result: sat_info(17),
+        agreed: vec![Provider::Hiro],
+        dissented: vec![],
+        failed: vec![ProviderOrdError{ provider: Provider::Bitgem, error: http_error() }],
+    });
+
+    // The successful responses still need to agree
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(17)) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Err(http_error()) },
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(sat_info(18)) },
+    ];
+    assert_eq!(reduce_results(ConsensusStrategy::Equality, true, results.clone()), MultiOrdResult::Inconsistent(results));
+
+    // The errors are returned when every provider fails
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Err(http_error()) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Err(http_error()) },
+    ];
+    assert_eq!(reduce_results(ConsensusStrategy::Equality, true, results.clone()), MultiOrdResult::Consistent(Err(http_error())));
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Err(http_error()) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Err(OrdError::ResponseError("Not found".to_string())) },
+    ];
+    assert_eq!(reduce_results(ConsensusStrategy::Equality, true, results.clone()), MultiOrdResult::Inconsistent(results));
+}
+
+#[test]
+fn test_historical_satributes() {
+
+    use crate::services::SERVICES;
+    use crate::types::{Provider, EndPoint};
+
+    // The responses are compared once mapped to the type shared by the providers, as done by request
+    let extract = |provider: Provider, bytes: &str| {
+        let service = SERVICES.get(&(provider, EndPoint::SatInfo)).unwrap();
+        service.common_response(service.extract_response(bytes.as_bytes()).unwrap()).unwrap()
+    };
+
+    // A pizza sat, only Bitgem knows about its historical satribute
+    let hiro = extract(Provider::Hiro, r#"{
+        "coinbase_height": 56400,
+        "cycle": 0,
+        "decimal": "56400.4319175359",
+        "degree": "0°56400′1968″4319175359‴",
+        "epoch": 0,
+        "name": "dpmzfmoutgsb",
+        "offset": 4319175359,
+        "percentile": "1.3428777103%",
+        "period": 27,
+        "rarity": "common"
+    }"#);
+    let bitgem = extract(Provider::Bitgem, r#"{
+        "sat": 282004319175359,
+        "height": 56400,
+        "cycle": 0,
+        "epoch": 0,
+        "period": 27,
+        "satributes": ["pizza"]
+    }"#);
+
+    let results = vec![
+        ProviderOrdResult{ provider: Provider::Hiro, result: Ok(hiro.clone()) },
+        ProviderOrdResult{ provider: Provider::Bitgem, result: Ok(bitgem) },
+    ];
+    assert_eq!(reduce_results(ConsensusStrategy::Equality, false, results), MultiOrdResult::Consistent(Ok(hiro)));
+}
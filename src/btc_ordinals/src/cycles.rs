@@ -0,0 +1,109 @@
+use ic_cdk::api::call::{msg_cycles_accept128, msg_cycles_available128};
+
+use crate::types::OrdError;
+
+use std::{cell::RefCell, rc::Rc};
+
+/// Number of nodes of an application subnet, assumed unless configured otherwise.
+pub const DEFAULT_SUBNET_SIZE: u32 = 13;
+
+/// Cycles cost constants, based on
+/// https://internetcomputer.org/docs/current/developer-docs/gas-cost#details-cost-of-compute-and-storage-transactions-on-the-internet-computer
+/// The ingress costs are given for a 13-node subnet and scale linearly with the subnet size.
+const INGRESS_MESSAGE_RECEIVED_COST: u128 = 1_200_000;
+const INGRESS_MESSAGE_BYTE_RECEIVED_COST: u128 = 2_000;
+const INGRESS_REFERENCE_SUBNET_SIZE: u128 = 13;
+/// The HTTP outcall costs are given per node, the base cost growing quadratically with the subnet size:
+/// (3_000_000 + 60_000 * n) * n + 400 * n * request_bytes + 800 * n * max_response_bytes
+const HTTP_OUTCALL_BASE_COST: u128 = 3_000_000;
+const HTTP_OUTCALL_NODE_COST: u128 = 60_000;
+const HTTP_OUTCALL_BYTE_SENT_COST: u128 = 400;
+const HTTP_OUTCALL_BYTE_RECEIVED_COST: u128 = 800;
+
+/// The cost of an HTTP outcall, charged upfront for the maximum response size.
+pub fn http_outcall_cost(subnet_size: u32, request_bytes: u64, max_response_bytes: u64) -> u128 {
+    let nodes = subnet_size as u128;
+    (HTTP_OUTCALL_BASE_COST + HTTP_OUTCALL_NODE_COST * nodes) * nodes
+        + HTTP_OUTCALL_BYTE_SENT_COST * nodes * request_bytes as u128
+        + HTTP_OUTCALL_BYTE_RECEIVED_COST * nodes * max_response_bytes as u128
+}
+
+/// The cost of receiving a message of the given size, i.e. of the response of an HTTP outcall
+/// (for the call to transform context).
+pub fn ingress_message_cost(subnet_size: u32, message_bytes: u64) -> u128 {
+    (INGRESS_MESSAGE_RECEIVED_COST + INGRESS_MESSAGE_BYTE_RECEIVED_COST * message_bytes as u128)
+        * subnet_size as u128 / INGRESS_REFERENCE_SUBNET_SIZE
+}
+
+#[derive(Default)]
+struct Balances {
+    // Cycles set aside for the outcalls that are still pending
+    reserved: u128,
+    // Cycles accepted for the outcalls that completed
+    charged: u128,
+}
+
+/// Keeps track of the cycles attached by the caller. Before an outcall is sent, its worst case
+/// cost is reserved. Once it completes, only the cycles it actually consumed are accepted. The
+/// cycles that are not accepted are refunded to the caller when the call returns.
+/// The ledger is shared between the outcalls executed concurrently for the same call.
+#[derive(Clone, Default)]
+pub struct CyclesLedger {
+    balances: Rc<RefCell<Balances>>,
+}
+
+impl CyclesLedger {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves the given cycles, fails if the caller did not attach enough cycles.
+    pub fn reserve(&self, cycles: u128) -> Result<(), OrdError> {
+        let mut balances = self.balances.borrow_mut();
+        // The available cycles do not include the ones already accepted.
+        let available = msg_cycles_available128();
+        let expected = balances.reserved + cycles;
+        if available < expected {
+            return Err(OrdError::TooFewCycles { expected, received: available });
+        }
+        balances.reserved = expected;
+        Ok(())
+    }
+
+    /// Releases the cycles previously reserved and accepts the ones actually consumed.
+    pub fn charge(&self, reserved: u128, consumed: u128) {
+        let mut balances = self.balances.borrow_mut();
+        balances.reserved = balances.reserved.saturating_sub(reserved);
+        balances.charged += msg_cycles_accept128(consumed.min(reserved));
+    }
+
+    /// The total of cycles accepted so far.
+    pub fn charged(&self) -> u128 {
+        self.balances.borrow().charged
+    }
+}
+
+#[test]
+fn test_application_subnet_costs() {
+    // Matches the costs published for a 13-node subnet
+    assert_eq!(http_outcall_cost(13, 0, 0), 49_140_000);
+    assert_eq!(http_outcall_cost(13, 1, 0) - http_outcall_cost(13, 0, 0), 5_200);
+    assert_eq!(http_outcall_cost(13, 0, 1) - http_outcall_cost(13, 0, 0), 10_400);
+    assert_eq!(http_outcall_cost(13, 200, 2_048), 49_140_000 + 5_200 * 200 + 10_400 * 2_048);
+    assert_eq!(ingress_message_cost(13, 0), 1_200_000);
+    assert_eq!(ingress_message_cost(13, 1_024), 1_200_000 + 2_000 * 1_024);
+}
+
+#[test]
+fn test_fiduciary_subnet_costs() {
+    // (3_000_000 + 60_000 * 34) * 34
+    assert_eq!(http_outcall_cost(34, 0, 0), 171_360_000);
+    assert_eq!(http_outcall_cost(34, 1, 0) - http_outcall_cost(34, 0, 0), 13_600);
+    assert_eq!(http_outcall_cost(34, 0, 1) - http_outcall_cost(34, 0, 0), 27_200);
+    assert_eq!(http_outcall_cost(34, 200, 2_048), 171_360_000 + 13_600 * 200 + 27_200 * 2_048);
+    assert_eq!(ingress_message_cost(34, 0), 3_138_461);
+    assert_eq!(ingress_message_cost(34, 1_024), (1_200_000 + 2_000 * 1_024) * 34 / 13);
+    // The larger subnet is always more expensive
+    assert!(http_outcall_cost(34, 200, 2_048) > http_outcall_cost(13, 200, 2_048));
+}
@@ -1,6 +1,7 @@
 use candid::Func;
 
 use ic_cdk::{
+    api::call::msg_cycles_refunded128,
     api::management_canister::http_request::{
         http_request, CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse,
         TransformContext, TransformFunc,
@@ -60,6 +61,15 @@ impl CanisterHttpRequest {
       self
     }
 
+    /// Adds the given headers, replacing the existing ones with the same name.
+    pub fn headers(mut self, headers: Vec<HttpHeader>) -> Self {
+        for header in headers {
+            self.args.headers.retain(|existing| !existing.name.eq_ignore_ascii_case(&header.name));
+            self.args.headers.push(header);
+        }
+        self
+    }
+
     /// Updates the URL in the `args` field.
     pub fn url(mut self, url: &str) -> Self {
         self.args.url = String::from(url);
@@ -86,6 +96,21 @@ impl CanisterHttpRequest {
         self
     }
 
+    /// The size of the request as priced by the HTTP outcalls: the URL, the headers,
+    /// the body and the transform function name and context.
+    pub fn request_bytes(&self) -> u64 {
+        let headers_bytes: usize = self.args.headers
+            .iter()
+            .map(|header| header.name.len() + header.value.len())
+            .sum();
+        let body_bytes = self.args.body.as_ref().map(|body| body.len()).unwrap_or(0);
+        let transform_bytes = self.args.transform
+            .as_ref()
+            .map(|transform| transform.function.0.method.len() + transform.context.len())
+            .unwrap_or(0);
+        (self.args.url.len() + headers_bytes + body_bytes + transform_bytes) as u64
+    }
+
     /// Updates the cycles of the request.
     pub fn cycles(mut self, cycles: u128) -> Self {
         self.cycles = cycles;
@@ -93,11 +118,85 @@ impl CanisterHttpRequest {
     }
 
     /// Wraps around `http_request` to issue a request to the `http_request` endpoint.
-    pub async fn send(self) -> Result<HttpResponse, HttpSendError> {
+    /// Also returns the cycles consumed by the call, i.e. the attached cycles minus the refunded ones.
+    pub async fn send(self) -> (Result<HttpResponse, HttpSendError>, u128) {
         
-        http_request(self.args, self.cycles)
-            .await
+        let result = http_request(self.args, self.cycles).await;
+
+        // The refund is only available right after the call returns, before any other await.
+        let consumed = self.cycles.saturating_sub(msg_cycles_refunded128());
+
+        let result = result
             .map(|(response,)| response)
-            .map_err(|(rejection_code, _)| HttpSendError{ rejection_code })
+            .map_err(|(rejection_code, _)| HttpSendError{ rejection_code });
+
+        (result, consumed)
+    }
+}
+
+/// Used to build the query string of a URL, the names and values being percent-encoded.
+#[derive(Clone, Debug, Default)]
+pub struct QueryString {
+    params: Vec<(String, String)>,
+}
+
+impl QueryString {
+    pub fn new() -> Self {
+        Self::default()
     }
+
+    /// Adds the given parameter.
+    pub fn param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds the given parameter, unless there is no value.
+    pub fn opt_param(self, name: &str, value: Option<impl ToString>) -> Self {
+        match value {
+            Some(value) => self.param(name, value),
+            None => self,
+        }
+    }
+
+    /// Adds the parameter once per value, as expected for a list.
+    pub fn list_param<T: ToString>(self, name: &str, values: &[T]) -> Self {
+        values.iter().fold(self, |query, value| query.param(name, value.to_string()))
+    }
+
+    /// Returns the query string, starting with '?' unless there is no parameter.
+    pub fn build(&self) -> String {
+        if self.params.is_empty() {
+            return String::new();
+        }
+        let params: Vec<String> = self.params
+            .iter()
+            .map(|(name, value)| format!("{}={}", percent_encode(name), percent_encode(value)))
+            .collect();
+        format!("?{}", params.join("&"))
+    }
+}
+
+/// Percent-encodes every byte but the unreserved characters of RFC 3986.
+fn percent_encode(value: &str) -> String {
+    value.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
+#[test]
+fn test_query_string() {
+    assert_eq!(QueryString::new().build(), "");
+    assert_eq!(
+        QueryString::new()
+            .param("offset", 0)
+            .opt_param("to_number", None::<u64>)
+            .opt_param("from_number", Some(10))
+            .list_param("mime_type", &["image/png", "text/html;charset=utf-8"])
+            .build(),
+        "?offset=0&from_number=10&mime_type=image%2Fpng&mime_type=text%2Fhtml%3Bcharset%3Dutf-8"
+    );
+    // The separators of the query string cannot be injected
+    assert_eq!(QueryString::new().param("address", "bc1&limit=1000#").build(), "?address=bc1%26limit%3D1000%23");
 }
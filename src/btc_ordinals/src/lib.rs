@@ -1,76 +1,95 @@
+mod cache;
+mod config;
+mod consensus;
+mod cycles;
 mod http;
+mod memory;
+mod ordinals;
 mod types;
 mod services;
 mod utils;
 
 use ic_cdk::api::management_canister::http_request::{HttpResponse, TransformArgs};
 
-use services::{SERVICES, default_args, unwrap_max_response_bytes, deduce_end_point, validate_providers};
+use futures::future::join_all;
+
+use services::{SERVICES, COMPOSITE_SERVICES, default_args, unwrap_max_response_bytes, deduce_end_point, validate_providers, fallback_providers};
 use types::{SatRanges, SatInfo, HiroSatInscription, HiroSatInscriptions, Provider, OrdFunction, Args, OrdArgs,
-    ProviderOrdResult, EndPoint, Response, OrdResult, OrdError, MultiOrdResult, HiroBrc20Details, HiroBrc20Holders,
-    SatRangeArgs, SatInfoArgs, SatInscriptionsArgs, InscriptionInfoArgs, InscriptionContentArgs, Brc20DetailsArgs, Brc20HoldersArgs};
-use utils::from_ord_args;
+    ProviderOrdResult, EndPoint, Response, OrdResult, OrdError, MultiOrdResult, HiroBrc20Details, HiroBrc20Holders, HiroBrc20Balances,
+    HiroRuneDetails, HiroRuneHolders, HiroRuneBalances, RuneDetailsArgs, RuneHoldersArgs, RuneBalancesArgs,
+    SatRangeArgs, SatInfoArgs, SatInscriptionsArgs, AddressInscriptionsArgs, InscriptionInfoArgs, InscriptionContentArgs, Brc20DetailsArgs, Brc20HoldersArgs, Brc20BalancesArgs,
+    CacheStats, ConsensusStrategy, InitArgs, ProviderConfig, LocalSatInfo};
+use utils::{from_ord_args, is_transient_failure, is_success_status, validate_function};
 
 use crate::http::CanisterHttpRequest;
+use crate::cache::CacheKey;
+use crate::cycles::CyclesLedger;
 
 /// Used for setting the max response bytes.
 const ONE_KIB: u64 = 1_024;
 
-// Used to approximate the real size of the HTTP request message?
-// TODO: to validate, copied from the ETC-RPC canister
-pub const INGRESS_OVERHEAD_BYTES: u128 = 100;
+/// Number of attempts made on a provider before falling back to the next one,
+/// in case of transient failures.
+const MAX_ATTEMPTS_PER_PROVIDER: u32 = 2;
 
-/// Cycles cost constants, based on
-/// https://internetcomputer.org/docs/current/developer-docs/gas-cost#details-cost-of-compute-and-storage-transactions-on-the-internet-computer
-/// Warning: This assumes the canister runs on an application subnet (13 nodes)
-pub const INGRESS_MESSAGE_RECEIVED_COST: u128 = 1_200_000;
-pub const INGRESS_MESSAGE_BYTE_RECEIVED_COST: u128 = 2_000;
-pub const HTTP_OUTCALL_REQUEST_COST: u128 = 49_140_000;
-pub const HTTP_OUTCALL_BYTE_SENT_COST: u128 = 5_200;
-pub const HTTP_OUTCALL_BYTE_RECEIVED_COST: u128 = 10_400;
+#[ic_cdk::init]
+fn init(args: Option<InitArgs>) {
+    config::apply(args);
+}
+
+#[ic_cdk::post_upgrade]
+fn post_upgrade(args: Option<InitArgs>) {
+    config::apply(args);
+}
 
 #[ic_cdk::update]
-async fn request(args: OrdArgs) -> MultiOrdResult {
+async fn request(args: OrdArgs) -> (MultiOrdResult, u128) {
 
     let prepared_requests = match prepare_requests(args.clone()) {
         Ok(prepared_requests) => {
             prepared_requests
         },
         Err(err) => {
-            return MultiOrdResult::Consistent(Err(err));
+            return (MultiOrdResult::Consistent(Err(err)), 0);
         }
     };
 
     // Early return if the caller doesn't have enough cycles to pay for all the services.
-    match pay_cycles(compute_total_cost(&prepared_requests)) {
+    let ledger = CyclesLedger::new();
+    match ledger.reserve(compute_total_cost(&prepared_requests)) {
         Ok(_) => {},
         Err(err) => {
-            return MultiOrdResult::Consistent(Err(err));
+            return (MultiOrdResult::Consistent(Err(err)), 0);
         }
     };
 
-    // Execute the requests.
-    // TODO: parallelize the calls
-    let mut results: Vec<ProviderOrdResult> = vec![];
-    for (provider, request) in prepared_requests {
-        results.push(ProviderOrdResult{ provider: provider, result: execute_request(request).await });
-    }
+    // Execute the requests, the providers that are not requested are used as fallbacks.
+    let end_point = deduce_end_point(args.function.clone());
+    let requested : Vec<Provider> = prepared_requests.iter().map(|(provider, _)| *provider).collect();
+    let results = execute_requests(
+        end_point,
+        from_ord_args(args.clone()),
+        prepared_requests,
+        fallback_providers(end_point, &requested),
+        &ledger,
+    ).await;
+
+    // Map the results to the type shared by the providers, then sort them.
+    let results = results.into_iter().map(|ProviderOrdResult{ provider, result }| ProviderOrdResult {
+        provider,
+        result: result.and_then(|response| match SERVICES.get(&(provider, end_point)) {
+            Some(service) => service.common_response(response),
+            None => Err(OrdError::NoServiceError{ providers: vec![provider], end_point }),
+        }),
+    }).collect();
 
-    // Sort the results.
-    match results.first() {
-        Some(first) => {
-            let equal = results.iter().all(|other| other.result == first.result);
-            if equal {
-                return MultiOrdResult::Consistent(first.result.clone());
-            } else {
-                return MultiOrdResult::Inconsistent(results);
-            }
-        },
-        None => {
-            // This should never happen, hence the panic.
-            panic!("No results");
-        }
-    }
+    let result = consensus::reduce_results(
+        args.consensus.unwrap_or(ConsensusStrategy::Equality),
+        args.ignore_errors.unwrap_or(false),
+        results
+    );
+
+    (result, ledger.charged())
 }
 
 #[ic_cdk::query]
@@ -82,91 +101,179 @@ async fn request_cost(args: OrdArgs) -> Result<u128, OrdError> {
 }
 
 #[ic_cdk::update]
-async fn bitgem_sat_range(args: SatRangeArgs) -> Result<SatRanges, OrdError> {
+async fn bitgem_sat_range(args: SatRangeArgs) -> (Result<SatRanges, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Bitgem, EndPoint::SatRange, default_args(OrdFunction::SatRange(args))).await;
 
-    call_service(Provider::Bitgem, EndPoint::SatRange, default_args(OrdFunction::SatRange(args))).await.map(|response| {
+    (result.map(|response| {
         match response {
             Response::SatRange(sat_ranges) => sat_ranges,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
 }
 
 #[ic_cdk::update]
-async fn bitgem_sat_info(args: SatInfoArgs) -> Result<SatInfo, OrdError> {
+async fn bitgem_sat_info(args: SatInfoArgs) -> (Result<SatInfo, OrdError>, u128) {
 
-    call_service(Provider::Bitgem, EndPoint::SatInfo, default_args(OrdFunction::SatInfo(args))).await.map(|response| {
+    let (result, cycles_charged) = call_service(Provider::Bitgem, EndPoint::SatInfo, default_args(OrdFunction::SatInfo(args))).await;
+
+    (result.map(|response| {
         match response {
             Response::SatInfo(ordinal_info) => ordinal_info,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
 }
 
 #[ic_cdk::update]
-async fn hiro_sat_info(args: SatInfoArgs) -> Result<SatInfo, OrdError> {
+async fn hiro_sat_info(args: SatInfoArgs) -> (Result<SatInfo, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::SatInfo, default_args(OrdFunction::SatInfo(args))).await;
 
-    call_service(Provider::Hiro, EndPoint::SatInfo, default_args(OrdFunction::SatInfo(args))).await.map(|response| {
+    (result.map(|response| {
         match response {
             Response::SatInfo(ordinal_info) => ordinal_info,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
+}
+
+/// Computes the sat info in-canister, hence for free and without relying on any provider.
+#[ic_cdk::query]
+fn local_sat_info(args: SatInfoArgs) -> Result<LocalSatInfo, OrdError> {
+    args.sat.parse().map(|sat| sat.info())
 }
 
 #[ic_cdk::update]
-async fn hiro_sat_inscriptions(args: SatInscriptionsArgs) -> Result<HiroSatInscriptions, OrdError> {
+async fn hiro_sat_inscriptions(args: SatInscriptionsArgs) -> (Result<HiroSatInscriptions, OrdError>, u128) {
 
-    call_service(Provider::Hiro, EndPoint::SatInscriptions, default_args(OrdFunction::SatInscriptions(args))).await.map(|response| {
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::SatInscriptions, default_args(OrdFunction::SatInscriptions(args))).await;
+
+    (result.map(|response| {
         match response {
             Response::SatInscriptions(inscriptions) => inscriptions,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
+}
+
+#[ic_cdk::update]
+async fn hiro_address_inscriptions(args: AddressInscriptionsArgs) -> (Result<HiroSatInscriptions, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::AddressInscriptions, default_args(OrdFunction::AddressInscriptions(args))).await;
+
+    (result.map(|response| {
+        match response {
+            Response::AddressInscriptions(inscriptions) => inscriptions,
+            _ => panic!("Unexpected response type"),
+        }
+    }), cycles_charged)
 }
 
 #[ic_cdk::update]
-async fn hiro_inscription_info(args: InscriptionInfoArgs) -> Result<HiroSatInscription, OrdError> {
+async fn hiro_inscription_info(args: InscriptionInfoArgs) -> (Result<HiroSatInscription, OrdError>, u128) {
+
+    // The Ord provider only returns the inscription info shared by the providers, not the full Hiro one
+    let (result, cycles_charged) = call_pinned_service(Provider::Hiro, EndPoint::InscriptionInfo, default_args(OrdFunction::InscriptionInfo(args))).await;
 
-    call_service(Provider::Hiro, EndPoint::InscriptionInfo, default_args(OrdFunction::InscriptionInfo(args))).await.map(|response| {
+    (result.map(|response| {
         match response {
-            Response::InscriptionInfo(inscription) => inscription,
+            Response::HiroInscriptionInfo(inscription) => inscription,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
 }
 
 #[ic_cdk::update]
-async fn hiro_inscription_content(args: InscriptionContentArgs) -> Result<Vec<u8>, OrdError> {
+async fn hiro_inscription_content(args: InscriptionContentArgs) -> (Result<Vec<u8>, OrdError>, u128) {
 
-    call_service(Provider::Hiro, EndPoint::InscriptionContent, default_args(OrdFunction::InscriptionContent(args))).await.map(|response| {
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::InscriptionContent, default_args(OrdFunction::InscriptionContent(args))).await;
+
+    (result.map(|response| {
         match response {
             Response::InscriptionContent(content) => content,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
 }
 
 #[ic_cdk::update]
-async fn hiro_brc20_details(args: Brc20DetailsArgs) -> Result<HiroBrc20Details, OrdError> {
+async fn hiro_brc20_details(args: Brc20DetailsArgs) -> (Result<HiroBrc20Details, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::Brc20Details, default_args(OrdFunction::Brc20Details(args))).await;
 
-    call_service(Provider::Hiro, EndPoint::Brc20Details, default_args(OrdFunction::Brc20Details(args))).await.map(|response| {
+    (result.map(|response| {
         match response {
             Response::Brc20Details(details) => details,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
 }
 
 #[ic_cdk::update]
-async fn hiro_brc20_holders(args: Brc20HoldersArgs) -> Result<HiroBrc20Holders, OrdError> {
+async fn hiro_brc20_holders(args: Brc20HoldersArgs) -> (Result<HiroBrc20Holders, OrdError>, u128) {
 
-    call_service(Provider::Hiro, EndPoint::Brc20Holders, default_args(OrdFunction::Brc20Holders(args))).await.map(|response| {
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::Brc20Holders, default_args(OrdFunction::Brc20Holders(args))).await;
+
+    (result.map(|response| {
         match response {
             Response::Brc20Holders(holders) => holders,
             _ => panic!("Unexpected response type"),
         }
-    })
+    }), cycles_charged)
+}
+
+#[ic_cdk::update]
+async fn hiro_brc20_balances(args: Brc20BalancesArgs) -> (Result<HiroBrc20Balances, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::Brc20Balances, default_args(OrdFunction::Brc20Balances(args))).await;
+
+    (result.map(|response| {
+        match response {
+            Response::Brc20Balances(balances) => balances,
+            _ => panic!("Unexpected response type"),
+        }
+    }), cycles_charged)
+}
+
+#[ic_cdk::update]
+async fn hiro_rune_details(args: RuneDetailsArgs) -> (Result<HiroRuneDetails, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::RuneDetails, default_args(OrdFunction::RuneDetails(args))).await;
+
+    (result.map(|response| {
+        match response {
+            Response::RuneDetails(details) => details,
+            _ => panic!("Unexpected response type"),
+        }
+    }), cycles_charged)
+}
+
+#[ic_cdk::update]
+async fn hiro_rune_holders(args: RuneHoldersArgs) -> (Result<HiroRuneHolders, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::RuneHolders, default_args(OrdFunction::RuneHolders(args))).await;
+
+    (result.map(|response| {
+        match response {
+            Response::RuneHolders(holders) => holders,
+            _ => panic!("Unexpected response type"),
+        }
+    }), cycles_charged)
+}
+
+#[ic_cdk::update]
+async fn hiro_rune_balances(args: RuneBalancesArgs) -> (Result<HiroRuneBalances, OrdError>, u128) {
+
+    let (result, cycles_charged) = call_service(Provider::Hiro, EndPoint::RuneBalances, default_args(OrdFunction::RuneBalances(args))).await;
+
+    (result.map(|response| {
+        match response {
+            Response::RuneBalances(balances) => balances,
+            _ => panic!("Unexpected response type"),
+        }
+    }), cycles_charged)
 }
 
 #[ic_cdk::query]
@@ -174,7 +281,63 @@ async fn cycles_balance() -> u64 {
     ic_cdk::api::canister_balance()
 }
 
-fn prepare_requests(args: OrdArgs) -> Result<Vec<(Provider, CanisterHttpRequest)>, OrdError> {
+#[ic_cdk::update(guard = "caller_is_controller")]
+fn invalidate_cache(end_point: EndPoint, function: Option<OrdFunction>) -> Result<u64, OrdError> {
+
+    if let Some(function) = &function {
+        validate_function(function)?;
+        let actual = deduce_end_point(function.clone());
+        if actual != end_point {
+            return Err(OrdError::EndPointMismatchError{ expected: end_point, actual });
+        }
+    }
+
+    Ok(cache::invalidate(end_point, function))
+}
+
+#[ic_cdk::update(guard = "caller_is_controller")]
+fn set_provider_config(provider_config: ProviderConfig) -> Result<(), OrdError> {
+    config::set_provider_config(provider_config)
+}
+
+#[ic_cdk::query]
+fn cache_stats() -> CacheStats {
+    cache::stats()
+}
+
+fn caller_is_controller() -> Result<(), String> {
+    if ic_cdk::api::is_controller(&ic_cdk::caller()) {
+        Ok(())
+    } else {
+        Err("Only a controller can call this method".to_string())
+    }
+}
+
+/// A request is either served from the cache, requires an HTTP outcall, or is composed
+/// of several requests whose responses get merged.
+#[derive(Clone, Debug)]
+enum PreparedRequest {
+    // The payloads are boxed, as they are much larger than the composite variant
+    Cached(Box<Response>),
+    Outcall{ request: Box<CanisterHttpRequest>, cache_key: CacheKey },
+    Composite{ parts: Vec<(EndPoint, Args, PreparedRequest)> },
+}
+
+impl PreparedRequest {
+    /// The cycles required to execute the request, a cache hit is free.
+    fn cycles(&self) -> u128 {
+        match self {
+            PreparedRequest::Cached(_) => 0,
+            PreparedRequest::Outcall{ request, .. } => request.cycles,
+            PreparedRequest::Composite{ parts } => parts.iter().map(|(_, _, request)| request.cycles()).sum(),
+        }
+    }
+}
+
+fn prepare_requests(args: OrdArgs) -> Result<Vec<(Provider, PreparedRequest)>, OrdError> {
+
+    // Check that the arguments are valid before spending any cycles.
+    validate_function(&args.function)?;
 
     // Check that the providers are available for this function.
     let end_point = deduce_end_point(args.function.clone());
@@ -186,13 +349,13 @@ fn prepare_requests(args: OrdArgs) -> Result<Vec<(Provider, CanisterHttpRequest)
     };
 
     // Early return if no provider is available.
-    if providers.len() == 0 {
+    if providers.is_empty() {
         return Err(OrdError::NoServiceError{ providers, end_point });
     }
 
     let prepared_requests = providers.iter().map(|provider| {
-        let request = prepare_request(provider.clone(), end_point.clone(), from_ord_args(args.clone()));
-        (provider.clone(), request.clone())
+        let request = prepare_request(*provider, end_point, from_ord_args(args.clone()));
+        (*provider, request)
     }).collect();
 
     Ok(prepared_requests)
@@ -202,7 +365,31 @@ fn prepare_request(
     provider: Provider,
     end_point: EndPoint,
     args: Args,
-) -> CanisterHttpRequest {
+) -> PreparedRequest {
+
+    // The parts of a composite request are cached individually.
+    if let Some(service) = COMPOSITE_SERVICES.get(&(provider, end_point)) {
+        let parts = service.get_parts(&args).into_iter().map(|(end_point, args)| {
+            let request = prepare_outcall(provider, end_point, args.clone());
+            (end_point, args, request)
+        }).collect();
+        return PreparedRequest::Composite{ parts };
+    }
+
+    prepare_outcall(provider, end_point, args)
+}
+
+/// Serves the request from the cache if possible, otherwise builds the outcall of the service.
+fn prepare_outcall(
+    provider: Provider,
+    end_point: EndPoint,
+    args: Args,
+) -> PreparedRequest {
+
+    let cache_key = CacheKey::new(provider, end_point, args.function.clone());
+    if let Some(response) = cache::get(&cache_key, ic_cdk::api::time()) {
+        return PreparedRequest::Cached(Box::new(response));
+    }
 
     if let Some(service) = SERVICES.get(&(provider, end_point)) {
 
@@ -214,91 +401,195 @@ fn prepare_request(
         let context = candid::encode_args((provider, end_point))
             .map_err(|error| format!("Failure while encoding context: {}", error)).unwrap();
 
-        let cost = get_http_request_cost(
-            url.as_str(),
-            body.clone().map(|body| body.len() as u64).unwrap_or(0),
-            max_response_bytes,
-        );
-
         let request = CanisterHttpRequest::new()
             .url(url.as_str())
             .method(http_method)
             .body(body.clone())
+            .headers(service.get_headers())
+            .headers(config::headers(provider))
             .transform_context("transform_http_response", context)
-            .max_response_bytes(max_response_bytes)
-            .cycles(cost);
+            .max_response_bytes(max_response_bytes);
 
-        return request;
+        let cost = get_http_request_cost(request.request_bytes(), max_response_bytes);
+        let request = request.cycles(cost);
+
+        return PreparedRequest::Outcall{ request: Box::new(request), cache_key };
     }
     
     panic!("No service for provider: {:?} and end point: {:?}", provider, end_point);
 }
 
-fn compute_total_cost(requests: &Vec<(Provider, CanisterHttpRequest)>) -> u128 {
-    requests.iter().map(|request| request.1.cycles).sum()
-}
-
-fn pay_cycles(cycles_cost: u128) -> Result<(), OrdError> {
-    // Check that the caller has enough cycles to pay for the request.
-    let cycles_available: u128 = ic_cdk::api::call::msg_cycles_available128();
-    if cycles_available < cycles_cost {
-        return Err(OrdError::TooFewCycles {
-            expected: cycles_cost,
-            received: cycles_available,
-        }
-        .into());
-    }
-    // Pay for the request.
-    ic_cdk::api::call::msg_cycles_accept128(cycles_cost);
-    Ok(())
+fn compute_total_cost(requests: &[(Provider, PreparedRequest)]) -> u128 {
+    requests.iter().map(|request| request.1.cycles()).sum()
 }
 
+/// Executes the request, the cycles of the request are expected to be reserved in the ledger.
+/// Once the request completes, only the cycles actually consumed are charged.
 async fn execute_request(
     request: CanisterHttpRequest,
+    ledger: &CyclesLedger,
 ) -> OrdResult {
-    let http_response = request
-        .send()
-        .await
-        .map_err(|error| OrdError::HttpSendError(error))?;
+    let reserved = request.cycles;
+
+    let (http_result, consumed) = request.send().await;
+
+    // Add the price of receiving the response, based on its actual size.
+    let consumed = consumed + http_result
+        .as_ref()
+        .map(|http_response| get_http_response_cost(http_response.body.len() as u64))
+        .unwrap_or(0);
+    ledger.charge(reserved, consumed);
+
+    let http_response = http_result
+        .map_err(OrdError::HttpSendError)?;
 
     candid::decode_args::<(OrdResult,)>(http_response.body.as_slice())
         .map(|decoded| decoded.0)
         .map_err(|error| OrdError::ResponseDecodingError(format!("Failure while decoding response: {}", error)))?
 }
 
-async fn call_service(
+/// Sends all the requests concurrently and waits for all of them to complete.
+/// The results are returned in the same order as the given requests.
+/// Note that execute_with_fallback shall never panic: a trap in one of the branches
+/// would discard the results of the others, hence every failure is mapped to an OrdError.
+async fn execute_requests(
+    end_point: EndPoint,
+    args: Args,
+    requests: Vec<(Provider, PreparedRequest)>,
+    fallbacks: Vec<Provider>,
+    ledger: &CyclesLedger,
+) -> Vec<ProviderOrdResult> {
+    let num_requests = requests.len();
+    join_all(requests.into_iter().enumerate().map(|(index, (provider, request))| {
+        // Each request gets its own share of the fallback providers, so that two
+        // requests never end up with the result of the same provider.
+        let fallbacks = fallbacks.iter().skip(index).step_by(num_requests).cloned().collect();
+        execute_with_fallback(provider, end_point, args.clone(), request, fallbacks, ledger)
+    })).await
+}
+
+/// Executes the request, retrying it on transient failures up to MAX_ATTEMPTS_PER_PROVIDER
+/// times, then falling back to the given providers in order. The cycles of the first attempt
+/// are expected to be already reserved, every subsequent attempt is reserved right before being sent.
+/// The returned provider is the one that produced the result.
+async fn execute_with_fallback(
     provider: Provider,
     end_point: EndPoint,
     args: Args,
+    request: PreparedRequest,
+    fallbacks: Vec<Provider>,
+    ledger: &CyclesLedger,
+) -> ProviderOrdResult {
+
+    let mut provider = provider;
+    let mut result = execute_prepared_request(provider, end_point, &args, request, ledger).await;
+    let mut attempts = 1;
+    let mut fallbacks = fallbacks.into_iter();
+
+    while is_transient_failure(&result) {
+        if attempts >= MAX_ATTEMPTS_PER_PROVIDER {
+            match fallbacks.next() {
+                Some(fallback) => {
+                    provider = fallback;
+                    attempts = 0;
+                },
+                None => break,
+            }
+        }
+        let request = prepare_request(provider, end_point, args.clone());
+        if let Err(err) = ledger.reserve(request.cycles()) {
+            result = Err(err);
+            break;
+        }
+        result = execute_prepared_request(provider, end_point, &args, request, ledger).await;
+        attempts += 1;
+    }
+
+    ProviderOrdResult{ provider, result }
+}
+
+/// Serves the cached response if any, otherwise executes the outcall and caches its response.
+/// The parts of a composite request are executed concurrently, then merged.
+async fn execute_prepared_request(
+    provider: Provider,
+    end_point: EndPoint,
+    args: &Args,
+    request: PreparedRequest,
+    ledger: &CyclesLedger,
 ) -> OrdResult {
+    match request {
+        PreparedRequest::Cached(response) => Ok(*response),
+        PreparedRequest::Outcall{ request, cache_key } => {
+            let response = execute_request(*request, ledger).await?;
+            let response = match SERVICES.get(&(provider, end_point)) {
+                Some(service) => service.complete_response(args, response)?,
+                None => return Err(OrdError::NoServiceError{ providers: vec![provider], end_point }),
+            };
+            cache::insert(cache_key, response.clone(), ic_cdk::api::time());
+            Ok(response)
+        },
+        PreparedRequest::Composite{ parts } => {
+            let responses = join_all(parts.into_iter().map(|(end_point, args, request)| async move {
+                Box::pin(execute_prepared_request(provider, end_point, &args, request, ledger)).await
+            })).await.into_iter().collect::<Result<Vec<_>, _>>()?;
+            match COMPOSITE_SERVICES.get(&(provider, end_point)) {
+                Some(service) => service.merge_responses(args, responses),
+                None => Err(OrdError::NoServiceError{ providers: vec![provider], end_point }),
+            }
+        }
+    }
+}
+
+/// Serves a provider specific function the same way as the request method: the transient failures
+/// are retried, then fall back to the next provider that serves the same end point.
+async fn call_service(
+    provider: Provider,
+    end_point: EndPoint,
+    args: Args,
+) -> (OrdResult, u128) {
+    call_service_with_fallbacks(provider, end_point, args, fallback_providers(end_point, &[provider])).await
+}
+
+/// Serves a provider specific function that must stay pinned to its provider, because the other
+/// providers of the end point return another response type: the transient failures are only retried.
+async fn call_pinned_service(
+    provider: Provider,
+    end_point: EndPoint,
+    args: Args,
+) -> (OrdResult, u128) {
+    call_service_with_fallbacks(provider, end_point, args, vec![]).await
+}
+
+async fn call_service_with_fallbacks(
+    provider: Provider,
+    end_point: EndPoint,
+    args: Args,
+    fallbacks: Vec<Provider>,
+) -> (OrdResult, u128) {
+
+    if let Err(err) = validate_function(&args.function) {
+        return (Err(err), 0);
+    }
 
     let request = prepare_request(provider, end_point, args.clone());
 
-    pay_cycles(request.cycles)?;
+    let ledger = CyclesLedger::new();
+    if let Err(err) = ledger.reserve(request.cycles()) {
+        return (Err(err), 0);
+    }
+
+    let result = execute_with_fallback(provider, end_point, args, request, fallbacks, &ledger).await.result;
 
-    execute_request(request).await
+    (result, ledger.charged())
 }
 
 #[ic_cdk::query]
 fn transform_http_response(args: TransformArgs) -> HttpResponse {
 
     let mut sanitized = args.response;
- 
-    let result: OrdResult = {
-        
-        let context_result = candid::decode_args::<(Provider, EndPoint)>(&args.context)
-            .map(|decoded| decoded);
-
-        match context_result {
-            Err(err) => Err(OrdError::ContextDecodingError(format!("Failed to decode context: {}", err))),
-            Ok((provider, end_point)) => {
-                match SERVICES.get(&(provider, end_point)) {
-                    None => Err(OrdError::NoServiceError{ providers: vec![provider], end_point }),
-                    Some(service) => service.extract_response(&sanitized.body),
-                }
-            }
-        }
-    };
+
+    let status = u16::try_from(&sanitized.status.0).unwrap_or(u16::MAX);
+    let result = extract_http_response(&args.context, status, &sanitized.body);
 
     let body = match candid::encode_args((result,)) {
         Ok(body) => body,
@@ -312,22 +603,53 @@ fn transform_http_response(args: TransformArgs) -> HttpResponse {
     sanitized
 }
 
-/// Calculates the baseline cost of sending a request using HTTP outcalls.
+/// Extracts the response of the service given in the context. Only a successful (2xx) response is
+/// extracted, any other status is an error, so that e.g. a 404 page is neither served nor cached.
+fn extract_http_response(context: &[u8], status: u16, body: &[u8]) -> OrdResult {
+    match candid::decode_args::<(Provider, EndPoint)>(context) {
+        Err(err) => Err(OrdError::ContextDecodingError(format!("Failed to decode context: {}", err))),
+        Ok(_) if !is_success_status(status) => Err(OrdError::HttpStatusError{ status }),
+        Ok((provider, end_point)) => {
+            match SERVICES.get(&(provider, end_point)) {
+                None => Err(OrdError::NoServiceError{ providers: vec![provider], end_point }),
+                Some(service) => service.extract_response(body),
+            }
+        }
+    }
+}
+
+/// Calculates the cost of receiving the response of an HTTP outcall (for the call to transform context),
+/// given its actual size.
+fn get_http_response_cost(response_bytes: u64) -> u128 {
+    cycles::ingress_message_cost(config::subnet_size(), response_bytes)
+}
+
+/// Calculates the baseline cost of sending a request using HTTP outcalls, for the subnet
+/// size set in the config.
 fn get_http_request_cost(
-    url: &str,
-    body_size_bytes: u64,
+    request_bytes: u64,
     max_response_bytes: u64,
 ) -> u128 {
-    // Assume the request size = size(body) + size(url) + ingress overhead
-    let request_bytes = body_size_bytes as u128 + url.len() as u128 + INGRESS_OVERHEAD_BYTES;
+    let subnet_size = config::subnet_size();
     // Take the worst case scenario where the response uses the maximum number of bytes.
-    let response_bytes = max_response_bytes as u128;
-    
     // Add the price of receiving the message (for the call to transform context) 
     // to the price of the http outcall.
-    INGRESS_MESSAGE_RECEIVED_COST
-        + INGRESS_MESSAGE_BYTE_RECEIVED_COST * response_bytes
-        + HTTP_OUTCALL_REQUEST_COST
-        + HTTP_OUTCALL_BYTE_SENT_COST * request_bytes
-        + HTTP_OUTCALL_BYTE_RECEIVED_COST * response_bytes
+    cycles::ingress_message_cost(subnet_size, max_response_bytes)
+        + cycles::http_outcall_cost(subnet_size, request_bytes, max_response_bytes)
+}
+
+#[test]
+fn test_extract_http_response() {
+
+    let context = candid::encode_args((Provider::Hiro, EndPoint::InscriptionContent)).unwrap();
+
+    assert_eq!(extract_http_response(&context, 200, "whatever".as_bytes()), Ok(Response::InscriptionContent("whatever".as_bytes().to_vec())));
+
+    // Only the rate limiting and the server errors are worth a retry
+    let not_found = extract_http_response(&context, 404, "<html>Not Found</html>".as_bytes());
+    assert_eq!(not_found, Err(OrdError::HttpStatusError{ status: 404 }));
+    assert!(!is_transient_failure(&not_found));
+    let unavailable = extract_http_response(&context, 503, "<html>Service Unavailable</html>".as_bytes());
+    assert_eq!(unavailable, Err(OrdError::HttpStatusError{ status: 503 }));
+    assert!(is_transient_failure(&unavailable));
 }
@@ -0,0 +1,24 @@
+use ic_stable_structures::{
+    memory_manager::{MemoryId, MemoryManager, VirtualMemory},
+    DefaultMemoryImpl,
+};
+
+use std::cell::RefCell;
+
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+/// Each stable structure gets its own virtual memory.
+/// Warning: never reuse or reorder these ids, otherwise the data would be corrupted on upgrade.
+pub const CACHE_MEMORY_ID: MemoryId = MemoryId::new(0);
+pub const CACHE_STATS_MEMORY_ID: MemoryId = MemoryId::new(1);
+pub const CACHE_EXPIRY_MEMORY_ID: MemoryId = MemoryId::new(2);
+pub const CONFIG_MEMORY_ID: MemoryId = MemoryId::new(3);
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+}
+
+pub fn get_memory(id: MemoryId) -> Memory {
+    MEMORY_MANAGER.with(|memory_manager| memory_manager.borrow().get(id))
+}
@@ -0,0 +1,9 @@
+//! Ordinal theory computed in-canister, i.e. without any HTTP outcall.
+//! See https://docs.ordinals.com/overview.html
+
+mod notation;
+mod range;
+mod sat;
+
+pub use range::exotic_ranges;
+pub use sat::Sat;
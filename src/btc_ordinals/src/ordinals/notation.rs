@@ -0,0 +1,207 @@
+use super::sat::{Sat, subsidy, epoch_starting_sat, SUBSIDY_HALVING_INTERVAL, DIFFCHANGE_INTERVAL, CYCLE_EPOCHS, EPOCHS};
+
+use crate::types::{OrdError, SatNotation};
+
+// Every halving, the period offset of the first block of the epoch increases by this much,
+// because the halving interval is not a multiple of the difficulty adjustment interval.
+const HALVING_INCREMENT: u64 = SUBSIDY_HALVING_INTERVAL % DIFFCHANGE_INTERVAL;
+
+impl SatNotation {
+
+    /// Parses the notation into the sat it refers to.
+    pub fn parse(&self) -> Result<Sat, OrdError> {
+        match self {
+            SatNotation::Integer(ordinal) => Sat::new(*ordinal),
+            SatNotation::Name(name) => Sat::from_name(name),
+            SatNotation::Degree(degree) => Sat::from_degree(degree),
+            SatNotation::Decimal(decimal) => Sat::from_decimal(decimal),
+            SatNotation::Percentile(percentile) => Sat::from_percentile(percentile),
+        }
+    }
+}
+
+impl Sat {
+
+    /// The degree notation: cycle°blocks since halving′blocks since adjustment″offset‴
+    pub fn degree(&self) -> String {
+        let height = self.height();
+        format!("{}°{}′{}″{}‴",
+            self.cycle(),
+            height % SUBSIDY_HALVING_INTERVAL,
+            height % DIFFCHANGE_INTERVAL,
+            self.offset())
+    }
+
+    /// The decimal notation: height.offset
+    pub fn decimal(&self) -> String {
+        format!("{}.{}", self.height(), self.offset())
+    }
+
+    /// The name of the sat, the names get shorter as the sats get mined.
+    pub fn name(&self) -> String {
+        let mut x = Self::SUPPLY - self.0;
+        let mut name = String::new();
+        while x > 0 {
+            name.push(char::from(b'a' + ((x - 1) % 26) as u8));
+            x = (x - 1) / 26;
+        }
+        name.chars().rev().collect()
+    }
+
+    /// The position of the sat in the whole supply, in percent.
+    pub fn percentile(&self) -> String {
+        format!("{}%", (self.0 as f64 / (Self::SUPPLY - 1) as f64) * 100.0)
+    }
+
+    /// The sat at the given offset in the subsidy of the block at the given height, if any.
+    pub fn from_height_and_offset(height: u64, offset: u64) -> Option<Self> {
+        let epoch = height / SUBSIDY_HALVING_INTERVAL;
+        if epoch >= EPOCHS || offset >= subsidy(epoch) {
+            return None;
+        }
+        Some(Self(epoch_starting_sat(epoch) + (height % SUBSIDY_HALVING_INTERVAL) * subsidy(epoch) + offset))
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, OrdError> {
+        let invalid = || OrdError::InvalidSatNotationError(SatNotation::Name(name.to_string()));
+        let mut x: u64 = 0;
+        for c in name.chars() {
+            if !c.is_ascii_lowercase() {
+                return Err(invalid());
+            }
+            x = x * 26 + (c as u64 - 'a' as u64) + 1;
+            if x > Self::SUPPLY {
+                return Err(invalid());
+            }
+        }
+        if x == 0 {
+            return Err(invalid());
+        }
+        Ok(Self(Self::SUPPLY - x))
+    }
+
+    pub fn from_degree(degree: &str) -> Result<Self, OrdError> {
+        let invalid = || OrdError::InvalidSatNotationError(SatNotation::Degree(degree.to_string()));
+
+        let (cycle, rest) = degree.split_once('°').ok_or_else(invalid)?;
+        let (epoch_offset, rest) = rest.split_once('′').ok_or_else(invalid)?;
+        let (period_offset, rest) = rest.split_once('″').ok_or_else(invalid)?;
+        let block_offset = rest.strip_suffix('‴').ok_or_else(invalid)?;
+
+        let parse = |number: &str| number.parse::<u64>().map_err(|_| invalid());
+        let (cycle, epoch_offset, period_offset, block_offset) =
+            (parse(cycle)?, parse(epoch_offset)?, parse(period_offset)?, parse(block_offset)?);
+
+        if epoch_offset >= SUBSIDY_HALVING_INTERVAL || period_offset >= DIFFCHANGE_INTERVAL {
+            return Err(invalid());
+        }
+
+        // The epoch within the cycle is deduced from the gap between the two offsets,
+        // which grows by HALVING_INCREMENT at each halving.
+        let gap = period_offset + SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS - epoch_offset;
+        if !gap.is_multiple_of(HALVING_INCREMENT) {
+            return Err(invalid());
+        }
+        let epoch = cycle
+            .checked_mul(CYCLE_EPOCHS)
+            .and_then(|epoch| epoch.checked_add(gap % DIFFCHANGE_INTERVAL / HALVING_INCREMENT))
+            .ok_or_else(invalid)?;
+
+        Self::from_height_and_offset(epoch.saturating_mul(SUBSIDY_HALVING_INTERVAL).saturating_add(epoch_offset), block_offset)
+            .ok_or_else(invalid)
+    }
+
+    pub fn from_decimal(decimal: &str) -> Result<Self, OrdError> {
+        let invalid = || OrdError::InvalidSatNotationError(SatNotation::Decimal(decimal.to_string()));
+
+        let (height, offset) = decimal.split_once('.').ok_or_else(invalid)?;
+        let height = height.parse::<u64>().map_err(|_| invalid())?;
+        let offset = offset.parse::<u64>().map_err(|_| invalid())?;
+
+        Self::from_height_and_offset(height, offset).ok_or_else(invalid)
+    }
+
+    pub fn from_percentile(percentile: &str) -> Result<Self, OrdError> {
+        let invalid = || OrdError::InvalidSatNotationError(SatNotation::Percentile(percentile.to_string()));
+
+        let percentile = percentile
+            .strip_suffix('%')
+            .ok_or_else(invalid)?
+            .parse::<f64>()
+            .map_err(|_| invalid())?;
+        if !(0.0..=100.0).contains(&percentile) {
+            return Err(invalid());
+        }
+
+        let last = (Self::SUPPLY - 1) as f64;
+        let n = (percentile / 100.0 * last).round();
+        if n > last {
+            return Err(invalid());
+        }
+        Ok(Self(n as u64))
+    }
+}
+
+
+#[test]
+fn test_round_trip() {
+    let sats = [0, 1, 85000000000, 947410401228752, 1_050_000_000_000_000, 2_067_187_500_000_000, Sat::SUPPLY - 1];
+    for n in sats {
+        let sat = Sat::new(n).unwrap();
+        assert_eq!(SatNotation::Integer(n).parse(), Ok(sat));
+        assert_eq!(SatNotation::Name(sat.name()).parse(), Ok(sat));
+        assert_eq!(SatNotation::Degree(sat.degree()).parse(), Ok(sat));
+        assert_eq!(SatNotation::Decimal(sat.decimal()).parse(), Ok(sat));
+    }
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(SatNotation::Name("nvtdijuwxlp".to_string()).parse(), Sat::new(0));
+    assert_eq!(SatNotation::Name("a".to_string()).parse(), Sat::new(Sat::SUPPLY - 1));
+    assert_eq!(SatNotation::Degree("0°0′0″0‴".to_string()).parse(), Sat::new(0));
+    assert_eq!(SatNotation::Degree("0°17′17″0‴".to_string()).parse(), Sat::new(85000000000));
+    assert_eq!(SatNotation::Degree("1°0′0″0‴".to_string()).parse(), Sat::new(2_067_187_500_000_000));
+    assert_eq!(SatNotation::Decimal("0.0".to_string()).parse(), Sat::new(0));
+    assert_eq!(SatNotation::Decimal("17.0".to_string()).parse(), Sat::new(85000000000));
+    assert_eq!(SatNotation::Percentile("0%".to_string()).parse(), Sat::new(0));
+    assert_eq!(SatNotation::Percentile("100%".to_string()).parse(), Sat::new(Sat::SUPPLY - 1));
+    assert_eq!(SatNotation::Percentile("0.004047619052071431%".to_string()).parse(), Sat::new(85000000000));
+    // The example of the ordinal theory handbook
+    for notation in [
+        SatNotation::Name("satoshi".to_string()),
+        SatNotation::Degree("3°111094′214″16797‴".to_string()),
+        SatNotation::Decimal("3891094.16797".to_string()),
+        SatNotation::Percentile("99.99971949060254%".to_string()),
+    ] {
+        assert_eq!(notation.parse(), Sat::new(2099994106992659), "{:?}", notation);
+    }
+}
+
+#[test]
+fn test_invalid_notations() {
+    let invalid_name = |name: &str| Err(OrdError::InvalidSatNotationError(SatNotation::Name(name.to_string())));
+    assert_eq!(Sat::from_name(""), invalid_name(""));
+    assert_eq!(Sat::from_name("Nvtdijuwxlp"), invalid_name("Nvtdijuwxlp"));
+    assert_eq!(Sat::from_name("nvtdijuwxlq"), invalid_name("nvtdijuwxlq"));
+
+    let invalid_degree = |degree: &str| Err(OrdError::InvalidSatNotationError(SatNotation::Degree(degree.to_string())));
+    assert_eq!(Sat::from_degree("0°0′0″"), invalid_degree("0°0′0″"));
+    // The epoch offset and the period offset of a block always differ by a multiple of 336
+    assert_eq!(Sat::from_degree("0°1′0″0‴"), invalid_degree("0°1′0″0‴"));
+    assert_eq!(Sat::from_degree("0°0′2016″0‴"), invalid_degree("0°0′2016″0‴"));
+    assert_eq!(Sat::from_degree("0°0′0″5000000000‴"), invalid_degree("0°0′0″5000000000‴"));
+    assert_eq!(Sat::from_degree("6°0′0″0‴"), invalid_degree("6°0′0″0‴"));
+
+    let invalid_decimal = |decimal: &str| Err(OrdError::InvalidSatNotationError(SatNotation::Decimal(decimal.to_string())));
+    assert_eq!(Sat::from_decimal("17"), invalid_decimal("17"));
+    assert_eq!(Sat::from_decimal("17.5000000000"), invalid_decimal("17.5000000000"));
+    assert_eq!(Sat::from_decimal("6930000.0"), invalid_decimal("6930000.0"));
+
+    let invalid_percentile = |percentile: &str| Err(OrdError::InvalidSatNotationError(SatNotation::Percentile(percentile.to_string())));
+    assert_eq!(Sat::from_percentile("50"), invalid_percentile("50"));
+    assert_eq!(Sat::from_percentile("100.1%"), invalid_percentile("100.1%"));
+    assert_eq!(Sat::from_percentile("-1%"), invalid_percentile("-1%"));
+
+    assert_eq!(SatNotation::Integer(Sat::SUPPLY).parse(), Err(OrdError::SatOutOfRangeError{ ordinal: Sat::SUPPLY }));
+}
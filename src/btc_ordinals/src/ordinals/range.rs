@@ -0,0 +1,232 @@
+use super::{Sat, sat::COIN_VALUE};
+use crate::types::{ExoticSatRange, SatRange, OrdError};
+
+/// Maximum number of sats checked by a call, whatever the number and the size of the ranges.
+/// Beyond it, the call fails rather than leaving out the exotic sats of the remaining ranges.
+const MAX_CHECKED_SATS: usize = 10_000;
+
+/// The exotic sats found in the given ranges, each one reported as a range of a single sat.
+/// These are found with arithmetic on the ordinal numbers: the first sat of every block is at
+/// least uncommon and the last one is black, the first and last sats of every bitcoin are alpha
+/// and omega, and the palindromes are enumerated from their first half. Hence only these sats
+/// are checked, the cost grows with the number of blocks, bitcoins and palindromes the ranges
+/// span, not with their size, and it is capped by MAX_CHECKED_SATS.
+pub fn exotic_ranges(ranges: &[SatRange]) -> Result<Vec<ExoticSatRange>, OrdError> {
+    let mut budget = MAX_CHECKED_SATS;
+    let mut exotic_ranges = vec![];
+    for range in ranges {
+        // One more sat than the budget is looked for, to tell whether some would be left unchecked
+        let candidates = candidates(range.start, range.end, budget + 1);
+        if candidates.len() > budget {
+            return Err(OrdError::SatRangeTooLargeError{ max_checked_sats: MAX_CHECKED_SATS as u64 });
+        }
+        budget -= candidates.len();
+        exotic_ranges.extend(candidates.into_iter().filter_map(|ordinal| exotic_range(range, ordinal)));
+    }
+    Ok(exotic_ranges)
+}
+
+// The sats within [start, end) that can be exotic, in increasing order and at most limit of them
+fn candidates(start: u64, end: u64, limit: usize) -> Vec<u64> {
+    let mut candidates = block_boundaries(start, end, limit);
+    candidates.extend(coin_boundaries(start, end, limit));
+    candidates.extend(palindromes(start, end, limit));
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates.truncate(limit);
+    candidates
+}
+
+// The first and last sats of the blocks that fall within [start, end), in increasing order
+fn block_boundaries(start: u64, end: u64, limit: usize) -> Vec<u64> {
+    let mut boundaries = vec![];
+    let mut height = match Sat::new(start) {
+        Ok(sat) => sat.height(),
+        Err(_) => return boundaries,
+    };
+    while let Some(first) = Sat::from_height_and_offset(height, 0).map(|sat| sat.n()) {
+        if first >= end || boundaries.len() >= limit {
+            break;
+        }
+        let last = Sat::from_height_and_offset(height + 1, 0).map_or(Sat::SUPPLY, |sat| sat.n()) - 1;
+        if first >= start {
+            boundaries.push(first);
+        }
+        if last != first && last < end {
+            boundaries.push(last);
+        }
+        height += 1;
+    }
+    boundaries
+}
+
+// The first and last sats of the bitcoins that fall within [start, end), in increasing order
+fn coin_boundaries(start: u64, end: u64, limit: usize) -> Vec<u64> {
+    let first_coin = start.div_ceil(COIN_VALUE) * COIN_VALUE;
+    let omegas = (start / COIN_VALUE + 1) * COIN_VALUE - 1;
+    let mut boundaries : Vec<u64> = (first_coin..end).step_by(COIN_VALUE as usize).take(limit).collect();
+    boundaries.extend((omegas..end).step_by(COIN_VALUE as usize).take(limit));
+    boundaries.sort_unstable();
+    boundaries.truncate(limit);
+    boundaries
+}
+
+// The palindromes within [start, end), in increasing order. Each one is given by its first
+// half, hence the ones of a given number of digits are enumerated from the first halves of
+// the bounds.
+fn palindromes(start: u64, end: u64, limit: usize) -> Vec<u64> {
+    let mut palindromes = vec![];
+    if start >= end {
+        return palindromes;
+    }
+    let digits = |n: u64| n.to_string().len() as u32;
+    for length in digits(start)..=digits(end - 1) {
+        let half = length.div_ceil(2);
+        let first_half = |n: u64| n / 10u64.pow(length - half);
+        let lowest = if length == digits(start) { first_half(start) } else { 10u64.pow(half - 1) };
+        let highest = if length == digits(end - 1) { first_half(end - 1) } else { 10u64.pow(half) - 1 };
+        for prefix in lowest..=highest {
+            let palindrome = mirror(prefix, length);
+            if palindrome >= end || palindromes.len() >= limit {
+                return palindromes;
+            }
+            if palindrome >= start {
+                palindromes.push(palindrome);
+            }
+        }
+    }
+    palindromes
+}
+
+// The palindrome of the given number of digits whose first half is the prefix
+fn mirror(prefix: u64, length: u32) -> u64 {
+    let digits = prefix.to_string();
+    let mirrored : String = digits.chars().rev().skip((length % 2) as usize).collect();
+    format!("{}{}", digits, mirrored).parse().expect("A palindrome fits in the sat range")
+}
+
+fn exotic_range(range: &SatRange, ordinal: u64) -> Option<ExoticSatRange> {
+    let sat = Sat::new(ordinal).ok()?;
+    let satributes = sat.satributes();
+    if satributes.is_empty() {
+        return None;
+    }
+    Some(ExoticSatRange {
+        utxo: range.utxo.clone(),
+        start: ordinal,
+        size: 1,
+        end: ordinal + 1,
+        offset: range.offset + (ordinal - range.start),
+        rarity: sat.rarity(),
+        satributes,
+    })
+}
+
+#[test]
+fn test_exotic_ranges() {
+
+    use crate::types::{SatoshiRarity, Satribute};
+
+    let range = |start: u64, end: u64, offset: u64| SatRange {
+        utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+        start,
+        size: end - start,
+        end,
+        offset,
+    };
+
+    // The first sat of a block is uncommon, the last one of the previous block is black
+    let ranges = vec![range(1846313750000000, 1846313750002121, 0), range(1846313749990000, 1846313750000000, 2121)];
+    assert_eq!(exotic_ranges(&ranges).unwrap(), vec![
+        ExoticSatRange {
+            utxo: ranges[0].utxo.clone(),
+            start: 1846313750000000,
+            size: 1,
+            end: 1846313750000001,
+            offset: 0,
+            rarity: SatoshiRarity::Uncommon,
+            satributes: vec![Satribute::Uncommon],
+        },
+        ExoticSatRange {
+            utxo: ranges[0].utxo.clone(),
+            start: 1846313749999999,
+            size: 1,
+            end: 1846313750000000,
+            offset: 2121 + 9999,
+            rarity: SatoshiRarity::Common,
+            satributes: vec![Satribute::Black],
+        },
+    ]);
+
+    // Every block within the range is found, not only the ones at its boundaries
+    let first = Sat::from_height_and_offset(840_001, 0).unwrap().n();
+    let second = Sat::from_height_and_offset(840_002, 0).unwrap().n();
+    let third = Sat::from_height_and_offset(840_003, 0).unwrap().n();
+    let exotic_sats : Vec<_> = exotic_ranges(&[range(first - 10, third + 10, 0)]).unwrap()
+        .into_iter()
+        .filter(|exotic_range| exotic_range.satributes.iter().any(|satribute| matches!(satribute, Satribute::Black | Satribute::Uncommon)))
+        .map(|exotic_range| (exotic_range.start, exotic_range.offset, exotic_range.rarity, exotic_range.satributes))
+        .collect();
+    assert_eq!(exotic_sats, vec![
+        (first - 1, 9, SatoshiRarity::Common, vec![Satribute::Black]),
+        (first, 10, SatoshiRarity::Uncommon, vec![Satribute::Uncommon]),
+        (second - 1, second - first + 9, SatoshiRarity::Common, vec![Satribute::Black]),
+        (second, second - first + 10, SatoshiRarity::Uncommon, vec![Satribute::Uncommon]),
+        (third - 1, third - first + 9, SatoshiRarity::Common, vec![Satribute::Black]),
+        (third, third - first + 10, SatoshiRarity::Uncommon, vec![Satribute::Uncommon]),
+    ]);
+
+    // The halving block is epic
+    let halving = Sat::from_height_and_offset(840_000, 0).unwrap().n();
+    assert_eq!(exotic_ranges(&[range(halving, halving + 1, 0)]).unwrap()[0].rarity, SatoshiRarity::Epic);
+
+    // Neither the common nor the empty ranges yield any exotic sat
+    assert_eq!(exotic_ranges(&[range(282004319175359, 282004319185359, 0), range(1846313750000000, 1846313750000000, 0)]), Ok(vec![]));
+}
+
+#[test]
+fn test_exotic_ranges_within_blocks() {
+
+    use crate::types::{SatoshiRarity, Satribute};
+
+    let range = |start: u64, end: u64| SatRange {
+        utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+        start,
+        size: end - start,
+        end,
+        offset: 0,
+    };
+    let exotic_sats = |start: u64, end: u64| -> Vec<_> {
+        exotic_ranges(&[range(start, end)])
+            .unwrap()
+            .into_iter()
+            .map(|exotic_range| (exotic_range.start, exotic_range.rarity, exotic_range.satributes))
+            .collect()
+    };
+
+    // The alpha and omega sats are found within a block, not only at its boundaries
+    assert_eq!(exotic_sats(1968750399999990, 1968750400000010), vec![
+        (1968750399999999, SatoshiRarity::Common, vec![Satribute::Omega]),
+        (1968750400000000, SatoshiRarity::Common, vec![Satribute::Alpha]),
+    ]);
+
+    // So are the palindromes
+    assert_eq!(exotic_sats(1968750330578600, 1968750330578700), vec![
+        (1968750330578691, SatoshiRarity::Common, vec![Satribute::Palindrome]),
+    ]);
+    assert_eq!(exotic_sats(1000, 1200), vec![
+        (1001, SatoshiRarity::Common, vec![Satribute::Vintage, Satribute::Palindrome]),
+        (1111, SatoshiRarity::Common, vec![Satribute::Vintage, Satribute::Palindrome]),
+    ]);
+
+    // The number of checked sats is capped, whatever the size of the ranges, and going beyond fails
+    // rather than leaving out some exotic sats
+    let error = Err(OrdError::SatRangeTooLargeError{ max_checked_sats: MAX_CHECKED_SATS as u64 });
+    assert_eq!(exotic_ranges(&[range(0, Sat::SUPPLY)]), error);
+    assert_eq!(exotic_ranges(&[range(0, 10_000_000_000), range(1_000_000_000_000_000, 1_000_001_000_000_000)]), error);
+
+    // Up to the cap, every candidate is checked
+    let last_candidate = candidates(0, Sat::SUPPLY, MAX_CHECKED_SATS)[MAX_CHECKED_SATS - 1];
+    assert!(exotic_ranges(&[range(0, last_candidate + 1)]).is_ok());
+    assert_eq!(exotic_ranges(&[range(0, last_candidate + 1), range(last_candidate + 1, Sat::SUPPLY)]), error);
+}
@@ -0,0 +1,248 @@
+use crate::types::{LocalSatInfo, OrdError, SatoshiRarity, Satribute};
+use crate::utils::map_rarity_satribute;
+
+pub(super) const COIN_VALUE: u64 = 100_000_000;
+pub(super) const SUBSIDY_HALVING_INTERVAL: u64 = 210_000;
+pub(super) const DIFFCHANGE_INTERVAL: u64 = 2_016;
+pub(super) const CYCLE_EPOCHS: u64 = 6;
+// The subsidy is 0 from the 33rd halving on, hence no sat is mined after
+pub(super) const EPOCHS: u64 = 33;
+
+/// A satoshi, identified by its ordinal number, i.e. the order in which it was mined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Sat(pub(super) u64);
+
+impl Sat {
+
+    /// The total number of sats that will ever be mined.
+    pub const SUPPLY: u64 = 2_099_999_997_690_000;
+
+    pub fn n(&self) -> u64 {
+        self.0
+    }
+
+    pub fn new(ordinal: u64) -> Result<Self, OrdError> {
+        if ordinal >= Self::SUPPLY {
+            return Err(OrdError::SatOutOfRangeError{ ordinal });
+        }
+        Ok(Self(ordinal))
+    }
+
+    /// The halving epoch the sat was mined in.
+    pub fn epoch(&self) -> u64 {
+        (1..EPOCHS)
+            .take_while(|epoch| epoch_starting_sat(*epoch) <= self.0)
+            .last()
+            .unwrap_or(0)
+    }
+
+    /// The height of the block the sat was mined in.
+    pub fn height(&self) -> u64 {
+        let epoch = self.epoch();
+        epoch * SUBSIDY_HALVING_INTERVAL + (self.0 - epoch_starting_sat(epoch)) / subsidy(epoch)
+    }
+
+    /// The number of conjunctions (coincidence of a halving and a difficulty adjustment)
+    /// that happened before the sat was mined.
+    pub fn cycle(&self) -> u64 {
+        self.height() / (SUBSIDY_HALVING_INTERVAL * CYCLE_EPOCHS)
+    }
+
+    /// The difficulty adjustment period the sat was mined in.
+    pub fn period(&self) -> u64 {
+        self.height() / DIFFCHANGE_INTERVAL
+    }
+
+    /// The index of the sat in the subsidy of its block.
+    pub fn offset(&self) -> u64 {
+        let epoch = self.epoch();
+        (self.0 - epoch_starting_sat(epoch)) % subsidy(epoch)
+    }
+
+    pub fn rarity(&self) -> SatoshiRarity {
+        let height = self.height();
+        let halving = height.is_multiple_of(SUBSIDY_HALVING_INTERVAL);
+        let adjustment = height.is_multiple_of(DIFFCHANGE_INTERVAL);
+        if self.offset() != 0 {
+            SatoshiRarity::Common
+        } else if self.0 == 0 {
+            SatoshiRarity::Mythic
+        } else if halving && adjustment {
+            SatoshiRarity::Legendary
+        } else if halving {
+            SatoshiRarity::Epic
+        } else if adjustment {
+            SatoshiRarity::Rare
+        } else {
+            SatoshiRarity::Uncommon
+        }
+    }
+
+    /// The satributes that follow from the ordinal number. The ones that depend on the
+    /// history of the sat (e.g. nakamoto, pizza) are only known by the providers.
+    pub fn satributes(&self) -> Vec<Satribute> {
+        let height = self.height();
+        let digits = self.0.to_string();
+        let mut satributes : Vec<Satribute> = map_rarity_satribute(&self.rarity()).into_iter().collect();
+        if self.offset() == subsidy(self.epoch()) - 1 {
+            satributes.push(Satribute::Black);
+        }
+        if height < 1_000 {
+            satributes.push(Satribute::Vintage);
+        }
+        if self.0.is_multiple_of(COIN_VALUE) {
+            satributes.push(Satribute::Alpha);
+        }
+        if self.0 % COIN_VALUE == COIN_VALUE - 1 {
+            satributes.push(Satribute::Omega);
+        }
+        if digits.chars().eq(digits.chars().rev()) {
+            satributes.push(Satribute::Palindrome);
+        }
+        if height == 9 {
+            satributes.push(Satribute::Block9);
+        }
+        if height == 78 {
+            satributes.push(Satribute::Block78);
+        }
+        satributes.sort();
+        satributes
+    }
+
+    pub fn info(&self) -> LocalSatInfo {
+        LocalSatInfo {
+            height: self.height(),
+            cycle: self.cycle(),
+            epoch: self.epoch(),
+            period: self.period(),
+            offset: self.offset(),
+            rarity: self.rarity(),
+            satributes: self.satributes(),
+            degree: self.degree(),
+            decimal: self.decimal(),
+            name: self.name(),
+            percentile: self.percentile(),
+        }
+    }
+}
+
+impl std::fmt::Display for Sat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// The block subsidy, in sats, of the given epoch
+pub(super) fn subsidy(epoch: u64) -> u64 {
+    (50 * COIN_VALUE) >> epoch
+}
+
+// The ordinal of the first sat mined in the given epoch
+pub(super) fn epoch_starting_sat(epoch: u64) -> u64 {
+    (0..epoch).map(|epoch| subsidy(epoch) * SUBSIDY_HALVING_INTERVAL).sum()
+}
+
+#[test]
+fn test_supply() {
+    assert_eq!(epoch_starting_sat(EPOCHS), Sat::SUPPLY);
+    assert_eq!(subsidy(EPOCHS - 1), 1);
+    assert_eq!(subsidy(EPOCHS), 0);
+    assert_eq!(Sat::new(Sat::SUPPLY), Err(OrdError::SatOutOfRangeError{ ordinal: Sat::SUPPLY }));
+}
+
+#[test]
+fn test_epochs() {
+    assert_eq!(Sat::new(0).unwrap().epoch(), 0);
+    assert_eq!(Sat::new(1_049_999_999_999_999).unwrap().epoch(), 0);
+    assert_eq!(Sat::new(1_050_000_000_000_000).unwrap().epoch(), 1);
+    assert_eq!(Sat::new(1_050_000_000_000_000).unwrap().height(), 210_000);
+    assert_eq!(Sat::new(Sat::SUPPLY - 1).unwrap().epoch(), 32);
+    assert_eq!(Sat::new(Sat::SUPPLY - 1).unwrap().height(), 6_929_999);
+}
+
+#[test]
+fn test_names() {
+    assert_eq!(Sat::new(0).unwrap().name(), "nvtdijuwxlp");
+    assert_eq!(Sat::new(1).unwrap().name(), "nvtdijuwxlo");
+    assert_eq!(Sat::new(26).unwrap().name(), "nvtdijuwxkp");
+    assert_eq!(Sat::new(27).unwrap().name(), "nvtdijuwxko");
+    assert_eq!(Sat::new(Sat::SUPPLY - 1).unwrap().name(), "a");
+}
+
+#[test]
+fn test_rarities() {
+    assert_eq!(Sat::new(0).unwrap().rarity(), SatoshiRarity::Mythic);
+    assert_eq!(Sat::new(1).unwrap().rarity(), SatoshiRarity::Common);
+    assert_eq!(Sat::new(50 * COIN_VALUE).unwrap().rarity(), SatoshiRarity::Uncommon);
+    assert_eq!(Sat::new(50 * COIN_VALUE * DIFFCHANGE_INTERVAL).unwrap().rarity(), SatoshiRarity::Rare);
+    assert_eq!(Sat::new(epoch_starting_sat(1)).unwrap().rarity(), SatoshiRarity::Epic);
+    assert_eq!(Sat::new(epoch_starting_sat(CYCLE_EPOCHS)).unwrap().rarity(), SatoshiRarity::Legendary);
+    assert_eq!(Sat::new(epoch_starting_sat(CYCLE_EPOCHS)).unwrap().degree(), "1°0′0″0‴");
+}
+
+#[test]
+fn test_hiro_fixture() {
+
+    use crate::types::HiroSatInfo;
+    use crate::utils::{deserialize_response, map_str_rarity};
+
+    // Same response as in the Hiro sat info service test
+    let bytes = r#"{
+        "coinbase_height": 17,
+        "cycle": 0,
+        "decimal": "17.0",
+        "degree": "0°17′17″0‴",
+        "epoch": 0,
+        "name": "nvsstftmsmj",
+        "offset": 0,
+        "percentile": "0.004047619052071431%",
+        "period": 0,
+        "rarity": "uncommon"
+      }"#.as_bytes();
+    let hiro = deserialize_response::<HiroSatInfo>(bytes).unwrap();
+
+    let local = Sat::new(85000000000).unwrap().info();
+    assert_eq!(local.height, hiro.coinbase_height);
+    assert_eq!(local.cycle, hiro.cycle);
+    assert_eq!(local.epoch, hiro.epoch);
+    assert_eq!(local.period, hiro.period);
+    assert_eq!(local.offset, hiro.offset);
+    assert_eq!(Some(local.rarity), map_str_rarity(&hiro.rarity));
+    assert_eq!(local.degree, hiro.degree);
+    assert_eq!(local.decimal, hiro.decimal);
+    assert_eq!(local.name, hiro.name);
+    assert_eq!(local.percentile, hiro.percentile);
+}
+
+#[test]
+fn test_bitgem_fixtures() {
+
+    use crate::types::BitgemSatInfo;
+    use crate::utils::{deserialize_response, map_str_satributes};
+
+    // Same responses as in the Bitgem sat info service test
+    let fixtures = [
+        (r#"{ "sat":85000000000, "height":17, "cycle":0, "epoch":0, "period":0, "satributes":["uncommon","alpha","vintage"] }"#, SatoshiRarity::Uncommon),
+        (r#"{ "sat":6543210, "height":0, "cycle":0, "epoch":0, "period":0, "satributes":["vintage"] }"#, SatoshiRarity::Common),
+    ];
+    for (bytes, rarity) in fixtures {
+        let bitgem = deserialize_response::<BitgemSatInfo>(bytes.as_bytes()).unwrap();
+        let local = Sat::new(bitgem.sat).unwrap().info();
+        assert_eq!(local.height, bitgem.height);
+        assert_eq!(local.cycle, bitgem.cycle);
+        assert_eq!(local.epoch, bitgem.epoch);
+        assert_eq!(local.period, bitgem.period);
+        assert_eq!(local.rarity, rarity);
+        assert_eq!(local.satributes, map_str_satributes(&bitgem.satributes).1);
+    }
+}
+
+#[test]
+fn test_satributes() {
+    assert_eq!(Sat::new(0).unwrap().satributes(), vec![Satribute::Mythic, Satribute::Vintage, Satribute::Alpha, Satribute::Palindrome]);
+    assert_eq!(Sat::new(4_999_999_999).unwrap().satributes(), vec![Satribute::Black, Satribute::Vintage, Satribute::Omega]);
+    assert_eq!(Sat::new(45_000_000_000).unwrap().satributes(), vec![Satribute::Uncommon, Satribute::Vintage, Satribute::Alpha, Satribute::Block9]);
+    assert_eq!(Sat::new(390_000_000_123).unwrap().satributes(), vec![Satribute::Vintage, Satribute::Block78]);
+    assert_eq!(Sat::new(1_234_554_321).unwrap().satributes(), vec![Satribute::Vintage, Satribute::Palindrome]);
+    assert_eq!(Sat::new(1_050_000_000_000_001).unwrap().satributes(), vec![]);
+}
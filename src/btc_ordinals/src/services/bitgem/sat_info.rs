@@ -1,6 +1,6 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
 
-use crate::{types::{Provider, BitgemSatInfo, SatInfo, OrdResult, OrdError, SatInfoArgs}, utils::{map_str_rarity, deserialize_response}};
+use crate::{types::{Provider, BitgemSatInfo, SatInfo, OrdResult, SatInfoArgs}, utils::{map_str_satributes, arithmetic_satributes, deserialize_response}};
 
 use std::ops::Add;
 
@@ -10,11 +10,10 @@ impl IsService for ServiceBitgemSatInfo {
 
     fn get_url(&self, args: Args) -> String {
         let ordinal = match args.function {
-            OrdFunction::SatInfo(SatInfoArgs{ ordinal }) => ordinal,
+            OrdFunction::SatInfo(SatInfoArgs{ sat }) => sat.parse().expect("Invalid sat notation"),
             _ => panic!("Invalid function: SatInfo expected"),
         };
-        BASE_URLS[&Provider::Bitgem]
-            .clone()
+        base_url(Provider::Bitgem)
             .add(
                 format!("/sat/{}", ordinal)
                     .as_str()
@@ -23,32 +22,34 @@ impl IsService for ServiceBitgemSatInfo {
 
     fn extract_response(&self, bytes: &[u8]) -> OrdResult {
         let bitgem_sat_info = deserialize_response::<BitgemSatInfo>(bytes)?;
-        let rarities : Vec<_> = bitgem_sat_info.satributes
-            .iter()
-            .filter_map(|satribute| map_str_rarity(&satribute))
-            .collect();
-        let rarity = match rarities.len() {
-            0 => crate::types::SatoshiRarity::Common, // If no rarity is found, assume common
-            1 => rarities[0].clone(),
-            _ => return Err(OrdError::ResponseDecodingError(
-                    format!("Multiple rarities found in Bitgem satributes: {}", bitgem_sat_info.satributes.join(","))
-                )),
-        };
+        let (rarity, satributes) = map_str_satributes(&bitgem_sat_info.satributes);
         Ok(Response::SatInfo(SatInfo {
             height: bitgem_sat_info.height,
             cycle: bitgem_sat_info.cycle,
             epoch: bitgem_sat_info.epoch,
             period: bitgem_sat_info.period,
             rarity,
+            satributes,
         }))
     }
+
+    // The other providers only know the satributes which derive from the ordinal number
+    fn common_response(&self, response: Response) -> OrdResult {
+        match response {
+            Response::SatInfo(sat_info) => Ok(Response::SatInfo(SatInfo {
+                satributes: arithmetic_satributes(sat_info.satributes),
+                ..sat_info
+            })),
+            response => Ok(response),
+        }
+    }
 }
 
 #[test]
 fn test_build_request() {
     let service = ServiceBitgemSatInfo;
     let args = Args {
-        function: OrdFunction::SatInfo(SatInfoArgs{ ordinal: 85000000000 }),
+        function: OrdFunction::SatInfo(SatInfoArgs{ sat: crate::types::SatNotation::Integer(85000000000) }),
         max_kb_per_item: None,
     };
     assert_eq!(service.get_url(args.clone()), "https://api.bitgem.tech/sat/85000000000");
@@ -58,6 +59,9 @@ fn test_build_request() {
 
 #[test]
 fn test_extract_response() {
+
+    use crate::types::Satribute;
+
     let service = ServiceBitgemSatInfo;
     
     // Test with an uncommon sat
@@ -76,6 +80,7 @@ fn test_extract_response() {
         epoch: 0,
         period: 0,
         rarity: crate::types::SatoshiRarity::Uncommon,
+        satributes: vec![Satribute::Uncommon, Satribute::Vintage, Satribute::Alpha],
     }));
 
     // Test with a common sat
@@ -94,6 +99,50 @@ fn test_extract_response() {
         epoch: 0,
         period: 0,
         rarity: crate::types::SatoshiRarity::Common,
+        satributes: vec![Satribute::Vintage],
     }));
+
+    // Test with overlapping rarities, a historical and an unknown satribute
+    bytes = r#"{
+        "sat":45000000000,
+        "height":9,
+        "cycle":0,
+        "epoch":0,
+        "period":0,
+        "satributes":["block_9","uncommon","rare","vintage","Alpha","nakamoto","shiny"]
+    }"#.as_bytes();
+    response = service.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::SatInfo(SatInfo {
+        height: 9,
+        cycle: 0,
+        epoch: 0,
+        period: 0,
+        rarity: crate::types::SatoshiRarity::Rare,
+        satributes: vec![
+            Satribute::Uncommon,
+            Satribute::Rare,
+            Satribute::Vintage,
+            Satribute::Alpha,
+            Satribute::Block9,
+            Satribute::Nakamoto,
+            Satribute::Other("shiny".to_string()),
+        ],
+    }));
+
+    // Only the satributes which derive from the ordinal number are compared with the other providers
+    assert_eq!(service.common_response(response), Ok(Response::SatInfo(SatInfo {
+        height: 9,
+        cycle: 0,
+        epoch: 0,
+        period: 0,
+        rarity: crate::types::SatoshiRarity::Rare,
+        satributes: vec![
+            Satribute::Uncommon,
+            Satribute::Rare,
+            Satribute::Vintage,
+            Satribute::Alpha,
+            Satribute::Block9,
+        ],
+    })));
 }
 
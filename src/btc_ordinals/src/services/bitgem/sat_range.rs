@@ -1,15 +1,15 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url, exclude_common_ranges};
 
 use ic_cdk::api::management_canister::http_request::HttpMethod;
 
-use crate::{types::{Provider, BitgemSatRanges, ExoticSatRange, OrdResult, SatRangeArgs, SatRanges, SatoshiRarity}, utils::{deserialize_response, map_str_rarity}};
+use crate::{ordinals::exotic_ranges, types::{Provider, BitgemSatRanges, ExoticSatRange, OrdResult, SatRangeArgs, SatRanges}, utils::{deserialize_response, map_str_satributes, arithmetic_satributes}};
 
 pub struct ServiceBitgemSatRange;
 
 impl IsService for ServiceBitgemSatRange {
 
     fn get_url(&self, _: Args) -> String {
-        BASE_URLS[&Provider::Bitgem].clone() + "/utxo-ranges"
+        base_url(Provider::Bitgem) + "/utxo-ranges"
     }
 
     fn get_body(&self, args: Args) -> Option<Vec<u8>> {
@@ -23,7 +23,6 @@ impl IsService for ServiceBitgemSatRange {
         let utxo_list = utxo_strings.join(",");
 
         let body_json = format!(r#"{{"utxos": [{}], "excludeCommonRanges": {}}}"#, utxo_list, exclude_common_ranges);
-        print!("body_json: {}", body_json);
         Some(body_json.as_bytes().to_vec())
     }
 
@@ -32,24 +31,50 @@ impl IsService for ServiceBitgemSatRange {
     }
 
     fn extract_response(&self, bytes: &[u8]) -> OrdResult {
-        print!("response bytes: {}", String::from_utf8(bytes.to_vec()).unwrap());
         let bitgem_sat_ranges = deserialize_response::<BitgemSatRanges>(bytes)?;
-        let exotic_ranges : Vec<_> = bitgem_sat_ranges.exoticRanges.unwrap_or(vec![]).iter().map(|exotic_range| {
-            ExoticSatRange {
-                utxo: exotic_range.utxo.clone(),
-                start: exotic_range.start,
-                size: exotic_range.size,
-                end: exotic_range.end,
-                offset: exotic_range.offset,
-                satributes: exotic_range.satributes.clone(),
-                rarity: exotic_range.satributes.iter().find_map(|satribute| map_str_rarity(&satribute)).unwrap_or(SatoshiRarity::Common),
-            }
-        }).collect();
+        let exotic_ranges : Vec<_> = match bitgem_sat_ranges.exoticRanges {
+            Some(exotic_ranges) => exotic_ranges.iter().map(|exotic_range| {
+                let (rarity, satributes) = map_str_satributes(&exotic_range.satributes);
+                ExoticSatRange {
+                    utxo: exotic_range.utxo.clone(),
+                    start: exotic_range.start,
+                    size: exotic_range.size,
+                    end: exotic_range.end,
+                    offset: exotic_range.offset,
+                    satributes,
+                    rarity,
+                }
+            }).collect(),
+            // Some indexers only return the raw ranges, the exotic sats are then found in-canister
+            None => exotic_ranges(bitgem_sat_ranges.ranges.as_deref().unwrap_or_default())?,
+        };
         Ok(Response::SatRange(SatRanges{
             ranges: bitgem_sat_ranges.ranges,
-            exotic_ranges: (!exotic_ranges.is_empty()).then(|| exotic_ranges),
+            exotic_ranges: (!exotic_ranges.is_empty()).then_some(exotic_ranges),
         }))
     }
+
+    fn complete_response(&self, args: &Args, response: Response) -> OrdResult {
+        Ok(exclude_common_ranges(args, response))
+    }
+
+    // The other providers only know the satributes which derive from the ordinal number, the ranges
+    // which are only exotic for their historical or unknown satributes are left out
+    fn common_response(&self, response: Response) -> OrdResult {
+        match response {
+            Response::SatRange(SatRanges{ ranges, exotic_ranges }) => {
+                let exotic_ranges : Vec<_> = exotic_ranges.unwrap_or_default().into_iter().filter_map(|exotic_range| {
+                    let satributes = arithmetic_satributes(exotic_range.satributes);
+                    (!satributes.is_empty()).then_some(ExoticSatRange{ satributes, ..exotic_range })
+                }).collect();
+                Ok(Response::SatRange(SatRanges{
+                    ranges,
+                    exotic_ranges: (!exotic_ranges.is_empty()).then_some(exotic_ranges),
+                }))
+            },
+            response => Ok(response),
+        }
+    }
 }
 
 #[test]
@@ -101,7 +126,7 @@ fn test_extract_response_1() {
 #[test]
 fn test_extract_response_2() {
 
-    use crate::types::SatRange;
+    use crate::types::{SatRange, SatoshiRarity, Satribute};
     
     let service = ServiceBitgemSatRange;
     let bytes = r#"{
@@ -144,7 +169,7 @@ fn test_extract_response_2() {
                 end: 1846313750000001,
                 offset: 0,
                 rarity: SatoshiRarity::Uncommon,
-                satributes: vec!["uncommon".to_string()],
+                satributes: vec![Satribute::Uncommon],
             }
         ]),
     }));
@@ -152,7 +177,9 @@ fn test_extract_response_2() {
 
 #[test]
 fn test_extract_response_3() {
-    
+
+    use crate::types::{SatoshiRarity, Satribute};
+
     let service = ServiceBitgemSatRange;
     let bytes = r#"{
         "ranges":null,
@@ -195,7 +222,7 @@ fn test_extract_response_3() {
                 end: 282004319185359,
                 offset: 399575979,
                 rarity: SatoshiRarity::Common,
-                satributes: vec!["pizza".to_string()],
+                satributes: vec![Satribute::Pizza],
             },
             ExoticSatRange {
                 utxo: "0a4ae1923b59e545e82dc7067965fe02304635db665806dee76e7ead7e002d41:1".to_string(),
@@ -204,7 +231,7 @@ fn test_extract_response_3() {
                 end: 1865887500000001,
                 offset: 400041360,
                 rarity: SatoshiRarity::Uncommon,
-                satributes: vec!["uncommon".to_string(), "alpha".to_string()],
+                satributes: vec![Satribute::Uncommon, Satribute::Alpha],
             },
             ExoticSatRange {
                 utxo: "0a4ae1923b59e545e82dc7067965fe02304635db665806dee76e7ead7e002d41:1".to_string(),
@@ -213,7 +240,57 @@ fn test_extract_response_3() {
                 end: 1059760000000001,
                 offset: 506755725,
                 rarity: SatoshiRarity::Uncommon,
-                satributes: vec!["uncommon".to_string(), "alpha".to_string()],
+                satributes: vec![Satribute::Uncommon, Satribute::Alpha],
+            }
+        ]),
+    }));
+
+    // The pizza range is only exotic for a historical satribute, it is not compared with the other providers
+    let exotic_ranges = match service.common_response(response).unwrap() {
+        Response::SatRange(SatRanges{ exotic_ranges, .. }) => exotic_ranges.unwrap_or_default(),
+        response => panic!("Unexpected response: {:?}", response),
+    };
+    assert_eq!(exotic_ranges.iter().map(|exotic_range| exotic_range.start).collect::<Vec<_>>(), vec![1865887500000000, 1059760000000000]);
+}
+
+#[test]
+fn test_extract_raw_ranges() {
+
+    use crate::types::{SatRange, SatoshiRarity, Satribute};
+
+    let bytes = r#"{
+        "ranges":[
+            {
+                "utxo":"1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1",
+                "start":1846313750000000,
+                "size":2121,
+                "end":1846313750002121,
+                "offset":0
+            }
+        ],
+        "exoticRanges":null
+    }"#.as_bytes();
+
+    let response = ServiceBitgemSatRange.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::SatRange(SatRanges {
+        ranges: Some(vec![
+            SatRange {
+                utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+                start: 1846313750000000,
+                size: 2121,
+                end: 1846313750002121,
+                offset: 0,
+            }
+        ]),
+        exotic_ranges: Some(vec![
+            ExoticSatRange {
+                utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+                start: 1846313750000000,
+                size: 1,
+                end: 1846313750000001,
+                offset: 0,
+                rarity: SatoshiRarity::Uncommon,
+                satributes: vec![Satribute::Uncommon],
             }
         ]),
     }));
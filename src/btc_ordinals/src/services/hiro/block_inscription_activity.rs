@@ -0,0 +1,113 @@
+use super::super::{IsCompositeService, Args, Response, OrdFunction, default_args};
+use super::map_inscription;
+
+use crate::types::{EndPoint, OrdError, OrdResult, BlockActivityArgs, BlockInscriptionActivity, InscriptionActivity,
+    SearchInscriptionsArgs, InscriptionsOrderBy, SortOrder};
+
+// Combines the inscriptions created in the block, found with the genesis block filter of the
+// search, and the inscriptions transferred in the block, see ServiceHiroBlockTransfers
+pub struct ServiceHiroBlockInscriptionActivity;
+
+impl IsCompositeService for ServiceHiroBlockInscriptionActivity {
+
+    fn get_parts(&self, args: &Args) -> Vec<(EndPoint, Args)> {
+        let BlockActivityArgs{ block, offset, limit } = match &args.function {
+            OrdFunction::BlockInscriptionActivity(args) => args.clone(),
+            _ => panic!("Invalid function: BlockInscriptionActivity expected"),
+        };
+        let part = |function: OrdFunction| Args {
+            max_kb_per_item: args.max_kb_per_item.or(default_args(function.clone()).max_kb_per_item),
+            function,
+        };
+        vec![
+            (EndPoint::SearchInscriptions, part(OrdFunction::SearchInscriptions(SearchInscriptionsArgs{
+                genesis_block: Some(block.clone()),
+                order_by: Some(InscriptionsOrderBy::Number),
+                order: Some(SortOrder::Asc),
+                offset,
+                limit,
+                ..Default::default()
+            }))),
+            (EndPoint::BlockInscriptionActivity, part(OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block, offset, limit }))),
+        ]
+    }
+
+    fn merge_responses(&self, args: &Args, responses: Vec<Response>) -> OrdResult {
+        let (offset, limit) = match &args.function {
+            OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ offset, limit, .. }) => (*offset, *limit),
+            _ => panic!("Invalid function: BlockInscriptionActivity expected"),
+        };
+        match <[Response; 2]>::try_from(responses) {
+            Ok([Response::SearchInscriptions(inscriptions), Response::BlockInscriptionActivity(transfers)]) => {
+                let mut results = inscriptions.results
+                    .into_iter()
+                    .map(|inscription| map_inscription(inscription).map(InscriptionActivity::Inscribed))
+                    .collect::<Result<Vec<_>, _>>()?;
+                results.extend(transfers.results);
+                Ok(Response::BlockInscriptionActivity(BlockInscriptionActivity {
+                    limit,
+                    offset,
+                    total_inscriptions: inscriptions.total,
+                    total_transfers: transfers.total_transfers,
+                    results,
+                }))
+            },
+            Ok([Response::SearchInscriptions(_), response]) => Err(OrdError::UnexpectedResponseTypeError(Box::new(response))),
+            Ok([response, _]) => Err(OrdError::UnexpectedResponseTypeError(Box::new(response))),
+            Err(responses) => Err(OrdError::ResponseError(format!("Expected 2 responses, got {}", responses.len()))),
+        }
+    }
+}
+
+#[test]
+fn test_get_parts() {
+
+    use super::super::IsService;
+    use crate::types::BlockId;
+
+    let args = Args {
+        function: OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block: BlockId::Height(775617), offset: 0, limit: 10 }),
+        max_kb_per_item: None,
+    };
+    let parts = ServiceHiroBlockInscriptionActivity.get_parts(&args);
+    assert_eq!(parts.len(), 2);
+
+    // The parts use their own default max response size, unless one was given
+    let (end_point, search) = &parts[0];
+    assert_eq!(*end_point, EndPoint::SearchInscriptions);
+    assert_eq!(search.max_kb_per_item, Some(2));
+    assert_eq!(super::search_inscriptions::ServiceHiroSearchInscriptions.get_url(search.clone()),
+        "https://api.hiro.so/ordinals/v1/inscriptions?genesis_block=775617&order_by=number&order=asc&offset=0&limit=10");
+    let (end_point, transfers) = &parts[1];
+    assert_eq!(*end_point, EndPoint::BlockInscriptionActivity);
+    assert_eq!(transfers.max_kb_per_item, Some(2));
+    assert_eq!(super::block_transfers::ServiceHiroBlockTransfers.get_url(transfers.clone()),
+        "https://api.hiro.so/ordinals/v1/inscriptions/transfers?block=775617&offset=0&limit=10");
+
+    let args = Args { max_kb_per_item: Some(3), ..args };
+    let parts = ServiceHiroBlockInscriptionActivity.get_parts(&args);
+    assert!(parts.iter().all(|(_, part)| part.max_kb_per_item == Some(3)));
+}
+
+#[test]
+fn test_merge_responses() {
+
+    use crate::types::{BlockId, HiroSatInscriptions};
+
+    let args = Args {
+        function: OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block: BlockId::Height(775617), offset: 0, limit: 10 }),
+        max_kb_per_item: None,
+    };
+    let inscriptions = Response::SearchInscriptions(HiroSatInscriptions{ limit: 10, offset: 0, total: 0, results: vec![] });
+    let transfers = Response::BlockInscriptionActivity(BlockInscriptionActivity{ limit: 10, offset: 0, total_inscriptions: 0, total_transfers: 3, results: vec![] });
+
+    assert_eq!(ServiceHiroBlockInscriptionActivity.merge_responses(&args, vec![inscriptions.clone(), transfers.clone()]), Ok(Response::BlockInscriptionActivity(BlockInscriptionActivity {
+        limit: 10,
+        offset: 0,
+        total_inscriptions: 0,
+        total_transfers: 3,
+        results: vec![],
+    })));
+    assert_eq!(ServiceHiroBlockInscriptionActivity.merge_responses(&args, vec![transfers.clone(), inscriptions.clone()]), Err(OrdError::UnexpectedResponseTypeError(Box::new(transfers.clone()))));
+    assert!(ServiceHiroBlockInscriptionActivity.merge_responses(&args, vec![inscriptions]).is_err());
+}
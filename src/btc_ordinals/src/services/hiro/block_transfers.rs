@@ -0,0 +1,155 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{map_transfer, block_id_param};
+
+use crate::{http::QueryString, types::{Provider, HiroBlockTransfers, BlockInscriptionActivity, InscriptionActivity, BlockTransfer, OrdResult, BlockActivityArgs},
+    utils::deserialize_response};
+use std::ops::Add;
+
+// The transfers part of the block inscription activity, it is served by the service of that end
+// point and its response only holds the transfers, the inscriptions are merged in by the composite
+pub struct ServiceHiroBlockTransfers;
+
+impl IsService for ServiceHiroBlockTransfers {
+
+    fn get_url(&self, args: Args) -> String {
+        let (block, offset, limit) = match args.function {
+            OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block, offset, limit }) => (block, offset, limit),
+            _ => panic!("Invalid function: BlockInscriptionActivity expected"),
+        };
+        let query = QueryString::new()
+            .param("block", block_id_param(&block))
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add("/ordinals/v1/inscriptions/transfers")
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let transfers = deserialize_response::<HiroBlockTransfers>(bytes)?;
+        let results = transfers.results
+            .into_iter()
+            .map(|transfer| Ok(InscriptionActivity::Transferred(BlockTransfer {
+                id: transfer.id,
+                number: transfer.number,
+                from: map_transfer(transfer.from)?,
+                to: map_transfer(transfer.to)?,
+            })))
+            .collect::<Result<_, _>>()?;
+        Ok(Response::BlockInscriptionActivity(BlockInscriptionActivity {
+            limit: transfers.limit,
+            offset: transfers.offset,
+            total_inscriptions: 0,
+            total_transfers: transfers.total,
+            results,
+        }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    use crate::types::BlockId;
+
+    let service = ServiceHiroBlockTransfers;
+    let args = Args {
+        function: OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block: BlockId::Height(804113), offset: 0, limit: 20 }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/inscriptions/transfers?block=804113&offset=0&limit=20");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+
+    let args = Args {
+        function: OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block: BlockId::Hash("00000000000000000001a0e7b3fb0a4c8b0f3f5c7f5b58b1c1e5a0b7e5a2e1f3".to_string()), offset: 20, limit: 20 }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args), "https://api.hiro.so/ordinals/v1/inscriptions/transfers?block=00000000000000000001a0e7b3fb0a4c8b0f3f5c7f5b58b1c1e5a0b7e5a2e1f3&offset=20&limit=20");
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::InscriptionTransfer;
+
+    let bytes = r#"{
+        "limit": 1,
+        "offset": 0,
+        "total": 1,
+        "results": [
+          {
+            "id": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0",
+            "number": 19095,
+            "from": {
+              "block_height": 775617,
+              "block_hash": "00000000000000000003e4523d5f3008bbf4deeaf8b6acca345241bfa9097d75",
+              "address": "bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td",
+              "tx_id": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc",
+              "location": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0",
+              "output": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0",
+              "value": "10000",
+              "offset": "0",
+              "timestamp": 1675884508000
+            },
+            "to": {
+              "block_height": 804113,
+              "block_hash": "00000000000000000001a0e7b3fb0a4c8b0f3f5c7f5b58b1c1e5a0b7e5a2e1f3",
+              "address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+              "tx_id": "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4",
+              "location": "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0:0",
+              "output": "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0",
+              "value": "546",
+              "offset": "0",
+              "timestamp": 1693616765000
+            }
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceHiroBlockTransfers.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::BlockInscriptionActivity(BlockInscriptionActivity {
+        limit: 1,
+        offset: 0,
+        total_inscriptions: 0,
+        total_transfers: 1,
+        results: vec![
+            InscriptionActivity::Transferred(BlockTransfer {
+                id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string(),
+                number: 19095,
+                from: InscriptionTransfer {
+                    block_height: 775617,
+                    block_hash: "00000000000000000003e4523d5f3008bbf4deeaf8b6acca345241bfa9097d75".to_string(),
+                    address: Some("bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td".to_string()),
+                    tx_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc".to_string(),
+                    location: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0".to_string(),
+                    output: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0".to_string(),
+                    value: 10000,
+                    offset: 0,
+                    timestamp: 1675884508,
+                },
+                to: InscriptionTransfer {
+                    block_height: 804113,
+                    block_hash: "00000000000000000001a0e7b3fb0a4c8b0f3f5c7f5b58b1c1e5a0b7e5a2e1f3".to_string(),
+                    address: Some("bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string()),
+                    tx_id: "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4".to_string(),
+                    location: "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0:0".to_string(),
+                    output: "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0".to_string(),
+                    value: 546,
+                    offset: 0,
+                    timestamp: 1693616765,
+                },
+            }),
+        ],
+    }));
+}
+
+#[test]
+fn test_validate_block() {
+
+    use crate::{types::{BlockId, OrdError}, utils::validate_function};
+
+    let activity = |block| OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block, offset: 0, limit: 20 });
+    assert_eq!(validate_function(&activity(BlockId::Height(804113))), Ok(()));
+    assert_eq!(validate_function(&activity(BlockId::Hash("00000000000000000001A0E7B3FB0A4C8B0F3F5C7F5B58B1C1E5A0B7E5A2E1F3".to_string()))), Ok(()));
+    assert_eq!(validate_function(&activity(BlockId::Hash("0000000000000000000".to_string()))), Err(OrdError::InvalidArgsError("Invalid block hash: 0000000000000000000".to_string())));
+}
@@ -0,0 +1,270 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{parse_brc20_amount, BRC20_DECIMALS};
+
+use crate::{http::QueryString, types::{Provider, HiroBrc20Activity, HiroBrc20ActivityEntry, Brc20Activity, Brc20ActivityEntry, Brc20Operation,
+    Brc20OperationKind, OrdError, OrdResult, Brc20ActivityArgs}, utils::{deserialize_response, normalize_address}};
+use std::ops::Add;
+
+pub struct ServiceBrc20Activity;
+
+impl IsService for ServiceBrc20Activity {
+
+    fn get_url(&self, args: Args) -> String {
+        let args : Brc20ActivityArgs = match args.function {
+            OrdFunction::Brc20Activity(args) => args,
+            _ => panic!("Invalid function: Brc20Activity expected"),
+        };
+        let operations : Vec<_> = args.operation.unwrap_or_default().iter().map(operation_param).collect();
+        let query = QueryString::new()
+            .list_param("ticker", &args.ticker.unwrap_or_default())
+            .list_param("operation", &operations)
+            .opt_param("block_height", args.block_height)
+            .opt_param("address", args.address.as_deref().map(normalize_address))
+            .param("offset", args.offset)
+            .param("limit", args.limit);
+        base_url(Provider::Hiro)
+            .add("/ordinals/v1/brc-20/activity")
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let activity = deserialize_response::<HiroBrc20Activity>(bytes)?;
+        let results = activity.results
+            .into_iter()
+            .map(map_entry)
+            .collect::<Result<_, _>>()?;
+        Ok(Response::Brc20Activity(Brc20Activity {
+            limit: activity.limit,
+            offset: activity.offset,
+            total: activity.total,
+            results,
+        }))
+    }
+}
+
+fn operation_param(operation: &Brc20OperationKind) -> &'static str {
+    match operation {
+        Brc20OperationKind::Deploy          => "deploy",
+        Brc20OperationKind::Mint            => "mint",
+        Brc20OperationKind::Transfer        => "transfer",
+        Brc20OperationKind::TransferSend    => "transfer_send",
+        Brc20OperationKind::TransferReceive => "transfer_receive",
+    }
+}
+
+fn map_entry(entry: HiroBrc20ActivityEntry) -> Result<Brc20ActivityEntry, OrdError> {
+    let missing = || OrdError::ResponseDecodingError(format!("Missing {} details", entry.operation));
+    let operation = match entry.operation.as_str() {
+        "deploy" => {
+            let deploy = entry.deploy.as_ref().ok_or_else(missing)?;
+            Brc20Operation::Deploy {
+                address: entry.address.clone(),
+                max_supply: parse_brc20_amount("max supply", &deploy.max_supply, deploy.decimals.into())?,
+                mint_limit: parse_brc20_amount("mint limit", &deploy.mint_limit, deploy.decimals.into())?,
+                decimals: deploy.decimals,
+            }
+        },
+        "mint" => Brc20Operation::Mint {
+            address: entry.address.clone(),
+            amount: parse_brc20_amount("amount", &entry.mint.as_ref().ok_or_else(missing)?.amount, BRC20_DECIMALS)?,
+        },
+        "transfer" => Brc20Operation::Transfer {
+            address: entry.address.clone(),
+            amount: parse_brc20_amount("amount", &entry.transfer.as_ref().ok_or_else(missing)?.amount, BRC20_DECIMALS)?,
+        },
+        "transfer_send" => {
+            let transfer_send = entry.transfer_send.as_ref().ok_or_else(missing)?;
+            Brc20Operation::TransferSend {
+                from_address: transfer_send.from_address.clone(),
+                to_address: transfer_send.to_address.clone(),
+                amount: parse_brc20_amount("amount", &transfer_send.amount, BRC20_DECIMALS)?,
+            }
+        },
+        // The receiving side of a transfer carries the same details as the sending one
+        "transfer_receive" => {
+            let transfer_receive = entry.transfer_send.as_ref().ok_or_else(missing)?;
+            Brc20Operation::TransferReceive {
+                from_address: transfer_receive.from_address.clone(),
+                to_address: transfer_receive.to_address.clone(),
+                amount: parse_brc20_amount("amount", &transfer_receive.amount, BRC20_DECIMALS)?,
+            }
+        },
+        operation => return Err(OrdError::ResponseDecodingError(format!("Unknown BRC-20 operation: {}", operation))),
+    };
+    Ok(Brc20ActivityEntry {
+        ticker: entry.ticker,
+        inscription_id: entry.inscription_id,
+        location: entry.location,
+        block_height: entry.block_height,
+        block_hash: entry.block_hash,
+        tx_id: entry.tx_id,
+        timestamp: entry.timestamp / 1000, // Hiro gives milliseconds
+        operation,
+    })
+}
+
+#[test]
+fn test_build_request() {
+
+    let service = ServiceBrc20Activity;
+
+    // Without any filter
+    let args = Args {
+        function: OrdFunction::Brc20Activity(Brc20ActivityArgs{ offset: 0, limit: 20, ..Default::default() }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/brc-20/activity?offset=0&limit=20");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+
+    // With every filter
+    let args = Args {
+        function: OrdFunction::Brc20Activity(Brc20ActivityArgs{
+            ticker: Some(vec!["ordi".to_string(), "sats".to_string()]),
+            operation: Some(vec![Brc20OperationKind::Deploy, Brc20OperationKind::TransferSend, Brc20OperationKind::TransferReceive]),
+            block_height: Some(779832),
+            address: Some("BC1PKCTWMTZ5LLXUU466HTG2DPPJ4LM29WNX28N2CXWWWM9XQUZRES9QQSHVSE".to_string()),
+            offset: 20,
+            limit: 20,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args), "https://api.hiro.so/ordinals/v1/brc-20/activity?ticker=ordi&ticker=sats\
+        &operation=deploy&operation=transfer_send&operation=transfer_receive&block_height=779832&address=bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse\
+        &offset=20&limit=20");
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::Brc20Amount;
+
+    let bytes = r#"{
+        "limit": 4,
+        "offset": 0,
+        "total": 4,
+        "results": [
+          {
+            "operation": "deploy",
+            "ticker": "ordi",
+            "inscription_id": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0",
+            "block_height": 779832,
+            "block_hash": "00000000000000000002b14f0c5dde0b2fc74d022e860696bd64f1f652756674",
+            "tx_id": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735",
+            "location": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735:0:0",
+            "address": "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06",
+            "timestamp": 1678248991000,
+            "deploy": {
+              "max_supply": "21000000.000000000000000000",
+              "mint_limit": "1000.000000000000000000",
+              "decimals": 18
+            }
+          },
+          {
+            "operation": "mint",
+            "ticker": "ordi",
+            "inscription_id": "1b8d0b42a9d8a8bbfa8f5ee4ab5ab8f02d0d2b54fcebc6d0e4fc62d8b9e1c4a2i0",
+            "block_height": 779971,
+            "block_hash": "0000000000000000000366ba5fda8e84d7ae6a8eb9cac5d8e2d3b2e6ef2f7e6d",
+            "tx_id": "1b8d0b42a9d8a8bbfa8f5ee4ab5ab8f02d0d2b54fcebc6d0e4fc62d8b9e1c4a2",
+            "location": "1b8d0b42a9d8a8bbfa8f5ee4ab5ab8f02d0d2b54fcebc6d0e4fc62d8b9e1c4a2:0:0",
+            "address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+            "timestamp": 1678330431000,
+            "mint": {
+              "amount": "1000.5"
+            }
+          },
+          {
+            "operation": "transfer_send",
+            "ticker": "ordi",
+            "inscription_id": "e3af144354367de58c675e987febcb49f17d6c19e645728b833fe95408feab85i0",
+            "block_height": 780005,
+            "block_hash": "00000000000000000001d5b29a4ec2a0b6e0f0c1ac8d0c7bc6a2e1e7d5ad8c1b",
+            "tx_id": "0a45f3bd2f4d3c8e6aa0b2a38e1c96e9e6b7f3a8d2cb1f1bdb8c0a5b2c6d9e1f",
+            "location": "0a45f3bd2f4d3c8e6aa0b2a38e1c96e9e6b7f3a8d2cb1f1bdb8c0a5b2c6d9e1f:0:0",
+            "address": "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3",
+            "timestamp": 1678350431000,
+            "transfer_send": {
+              "amount": "250.000000000000000001",
+              "from_address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+              "to_address": "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3"
+            }
+          },
+          {
+            "operation": "transfer_receive",
+            "ticker": "ordi",
+            "inscription_id": "e3af144354367de58c675e987febcb49f17d6c19e645728b833fe95408feab85i0",
+            "block_height": 780005,
+            "block_hash": "00000000000000000001d5b29a4ec2a0b6e0f0c1ac8d0c7bc6a2e1e7d5ad8c1b",
+            "tx_id": "0a45f3bd2f4d3c8e6aa0b2a38e1c96e9e6b7f3a8d2cb1f1bdb8c0a5b2c6d9e1f",
+            "location": "0a45f3bd2f4d3c8e6aa0b2a38e1c96e9e6b7f3a8d2cb1f1bdb8c0a5b2c6d9e1f:0:0",
+            "address": "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3",
+            "timestamp": 1678350431000,
+            "transfer_send": {
+              "amount": "250.000000000000000001",
+              "from_address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+              "to_address": "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3"
+            }
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceBrc20Activity.extract_response(bytes).unwrap();
+    let entries = match response {
+        Response::Brc20Activity(activity) => {
+            assert_eq!((activity.limit, activity.offset, activity.total), (4, 0, 4));
+            activity.results
+        },
+        _ => panic!("Unexpected response type"),
+    };
+    assert_eq!(entries[0].ticker, "ordi");
+    assert_eq!(entries[0].location, "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735:0:0");
+    assert_eq!(entries[0].timestamp, 1678248991);
+    assert_eq!(entries[0].operation, Brc20Operation::Deploy {
+        address: "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06".to_string(),
+        max_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+        mint_limit: Brc20Amount("1000000000000000000000".parse().unwrap()),
+        decimals: 18,
+    });
+    assert_eq!(entries[1].operation, Brc20Operation::Mint {
+        address: "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string(),
+        amount: Brc20Amount("1000500000000000000000".parse().unwrap()),
+    });
+    assert_eq!(entries[2].inscription_id, "e3af144354367de58c675e987febcb49f17d6c19e645728b833fe95408feab85i0");
+    assert_eq!(entries[2].operation, Brc20Operation::TransferSend {
+        from_address: "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string(),
+        to_address: "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3".to_string(),
+        amount: Brc20Amount("250000000000000000001".parse().unwrap()),
+    });
+    assert_eq!(entries[3].operation, Brc20Operation::TransferReceive {
+        from_address: "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string(),
+        to_address: "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3".to_string(),
+        amount: Brc20Amount("250000000000000000001".parse().unwrap()),
+    });
+}
+
+#[test]
+fn test_invalid_amount() {
+    let bytes = r#"{
+        "limit": 1,
+        "offset": 0,
+        "total": 1,
+        "results": [
+          {
+            "operation": "mint",
+            "ticker": "ordi",
+            "inscription_id": "1b8d0b42a9d8a8bbfa8f5ee4ab5ab8f02d0d2b54fcebc6d0e4fc62d8b9e1c4a2i0",
+            "block_height": 779971,
+            "block_hash": "0000000000000000000366ba5fda8e84d7ae6a8eb9cac5d8e2d3b2e6ef2f7e6d",
+            "tx_id": "1b8d0b42a9d8a8bbfa8f5ee4ab5ab8f02d0d2b54fcebc6d0e4fc62d8b9e1c4a2",
+            "location": "1b8d0b42a9d8a8bbfa8f5ee4ab5ab8f02d0d2b54fcebc6d0e4fc62d8b9e1c4a2:0:0",
+            "address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+            "timestamp": 1678330431000,
+            "mint": {
+              "amount": "-1000"
+            }
+          }
+        ]
+      }"#.as_bytes();
+    assert_eq!(ServiceBrc20Activity.extract_response(bytes), Err(OrdError::InvalidAmountError{ field: "amount".to_string(), amount: "-1000".to_string() }));
+}
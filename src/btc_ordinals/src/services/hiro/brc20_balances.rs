@@ -0,0 +1,120 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{parse_brc20_amount, BRC20_DECIMALS};
+
+use crate::{http::QueryString, types::{Provider, HiroBrc20Balances, HiroBrc20BalancesJson, HiroBrc20Balance, OrdResult, Brc20BalancesArgs}, utils::{deserialize_response, normalize_address}};
+use std::ops::Add;
+
+pub struct ServiceBrc20Balances;
+
+impl IsService for ServiceBrc20Balances {
+
+    fn get_url(&self, args: Args) -> String {
+        let (address, ticker, offset, limit) = match args.function {
+            OrdFunction::Brc20Balances(Brc20BalancesArgs{ address, ticker, offset, limit }) => (address, ticker, offset, limit),
+            _ => panic!("Invalid function: Brc20Balances expected"),
+        };
+        let query = QueryString::new()
+            .opt_param("ticker", ticker)
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add(format!("/ordinals/v1/brc-20/balances/{}", normalize_address(&address)).as_str())
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let balances = deserialize_response::<HiroBrc20BalancesJson>(bytes)?;
+        // The decimals of the tokens are unknown here, the ones of Hiro are assumed
+        let results = balances.results
+            .into_iter()
+            .map(|balance| Ok(HiroBrc20Balance {
+                available_balance: parse_brc20_amount("available balance", &balance.available_balance, BRC20_DECIMALS)?,
+                transferrable_balance: parse_brc20_amount("transferrable balance", &balance.transferrable_balance, BRC20_DECIMALS)?,
+                overall_balance: parse_brc20_amount("overall balance", &balance.overall_balance, BRC20_DECIMALS)?,
+                ticker: balance.ticker,
+            }))
+            .collect::<Result<_, _>>()?;
+        Ok(Response::Brc20Balances(HiroBrc20Balances {
+            limit: balances.limit,
+            offset: balances.offset,
+            total: balances.total,
+            results,
+        }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    let service = ServiceBrc20Balances;
+    let args = Args {
+        function: OrdFunction::Brc20Balances(Brc20BalancesArgs{
+            address: "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string(),
+            ticker: None,
+            offset: 0,
+            limit: 20,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/brc-20/balances/bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse?offset=0&limit=20");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+
+    let args = Args {
+        function: OrdFunction::Brc20Balances(Brc20BalancesArgs{
+            address: "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string(),
+            ticker: Some("ordi".to_string()),
+            offset: 0,
+            limit: 1,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args), "https://api.hiro.so/ordinals/v1/brc-20/balances/bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse?ticker=ordi&offset=0&limit=1");
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::Brc20Amount;
+
+    let bytes = r#"{
+        "limit": 2,
+        "offset": 0,
+        "total": 2,
+        "results": [
+          {
+            "ticker": "ordi",
+            "available_balance": "1500.000000000000000000",
+            "transferrable_balance": "500.000000000000000000",
+            "overall_balance": "2000.000000000000000000"
+          },
+          {
+            "ticker": "sats",
+            "available_balance": "100000000.000000000000000000",
+            "transferrable_balance": "0.000000000000000000",
+            "overall_balance": "100000000.000000000000000000"
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceBrc20Balances.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::Brc20Balances(HiroBrc20Balances {
+        limit: 2,
+        offset: 0,
+        total: 2,
+        results: vec![
+            HiroBrc20Balance {
+                ticker: "ordi".to_string(),
+                available_balance: Brc20Amount("1500000000000000000000".parse().unwrap()),
+                transferrable_balance: Brc20Amount("500000000000000000000".parse().unwrap()),
+                overall_balance: Brc20Amount("2000000000000000000000".parse().unwrap()),
+            },
+            HiroBrc20Balance {
+                ticker: "sats".to_string(),
+                available_balance: Brc20Amount("100000000000000000000000000".parse().unwrap()),
+                transferrable_balance: Brc20Amount("0000000000000000000".parse().unwrap()),
+                overall_balance: Brc20Amount("100000000000000000000000000".parse().unwrap()),
+            },
+        ],
+    }));
+}
@@ -1,6 +1,7 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{map_brc20_token, parse_brc20_amount};
 
-use crate::{types::{Provider, HiroBrc20Details, OrdResult, Brc20DetailsArgs}, utils::deserialize_response};
+use crate::{types::{Provider, HiroBrc20Details, HiroBrc20DetailsJson, HiroBrc20Supply, OrdResult, Brc20DetailsArgs}, utils::deserialize_response};
 use std::ops::Add;
 
 pub struct ServiceBrc20Details;
@@ -12,8 +13,7 @@ impl IsService for ServiceBrc20Details {
             OrdFunction::Brc20Details(Brc20DetailsArgs{ ticker }) => ticker,
             _ => panic!("Invalid function: Brc20Details expected"),
         };
-        BASE_URLS[&Provider::Hiro]
-            .clone()
+        base_url(Provider::Hiro)
             .add(
                 format!(
                     "/ordinals/v1/brc-20/tokens/{}",
@@ -24,8 +24,17 @@ impl IsService for ServiceBrc20Details {
     }
 
     fn extract_response(&self, bytes: &[u8]) -> OrdResult {
-        let brc20_details = deserialize_response::<HiroBrc20Details>(bytes)?;
-        Ok(Response::Brc20Details(brc20_details))
+        let brc20_details = deserialize_response::<HiroBrc20DetailsJson>(bytes)?;
+        let decimals = brc20_details.token.decimals;
+        let supply = brc20_details.supply;
+        Ok(Response::Brc20Details(HiroBrc20Details {
+            token: map_brc20_token(brc20_details.token)?,
+            supply: HiroBrc20Supply {
+                max_supply: parse_brc20_amount("max supply", &supply.max_supply, decimals)?,
+                minted_supply: parse_brc20_amount("minted supply", &supply.minted_supply, decimals)?,
+                holders: supply.holders,
+            },
+        }))
     }
 }
 
@@ -44,7 +53,7 @@ fn test_build_request() {
 #[test]
 fn test_extract_response() {
 
-    use crate::types::{HiroBrc20Token, HiroBrc20Supply};
+    use crate::types::{HiroBrc20Token, Brc20Amount};
 
     let bytes = r#"{
         "token": {
@@ -77,17 +86,69 @@ fn test_extract_response() {
             tx_id: "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735".to_string(),
             address: "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06".to_string(),
             ticker: "ordi".to_string(),
-            max_supply: "21000000.000000000000000000".to_string(),
-            mint_limit: "1000.000000000000000000".to_string(),
+            max_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+            mint_limit: Brc20Amount("1000000000000000000000".parse().unwrap()),
             decimals: 18,
             deploy_timestamp: 1678248991000,
-            minted_supply: "21000000.000000000000000000".to_string(),
+            minted_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
             tx_count: 225407,
         },
         supply: HiroBrc20Supply {
-            max_supply: "21000000.000000000000000000".to_string(),
-            minted_supply: "21000000.000000000000000000".to_string(),
+            max_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+            minted_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
             holders: 15120,
         },
     }));
 }
+
+#[test]
+fn test_invalid_amounts() {
+
+    use crate::types::OrdError;
+
+    let details = |decimals: u64, mint_limit: &str| format!(r#"{{
+        "token": {{
+          "id": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0",
+          "number": 348020,
+          "block_height": 779832,
+          "tx_id": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735",
+          "address": "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06",
+          "ticker": "ordi",
+          "max_supply": "21000000.000000000000000000",
+          "mint_limit": "{}",
+          "decimals": {},
+          "deploy_timestamp": 1678248991000,
+          "minted_supply": "21000000.000000000000000000",
+          "tx_count": 225407
+        }},
+        "supply": {{
+          "max_supply": "21000000.000000000000000000",
+          "minted_supply": "21000000.000000000000000000",
+          "holders": 15120
+        }}
+      }}"#, mint_limit, decimals);
+    let invalid = |mint_limit: &str| Err(OrdError::InvalidAmountError{ field: "mint limit".to_string(), amount: mint_limit.to_string() });
+
+    // The decimals of the token are honoured
+    assert!(ServiceBrc20Details.extract_response(details(1, "1000.500000000000000000").as_bytes()).is_ok());
+    assert_eq!(ServiceBrc20Details.extract_response(details(0, "1000.500000000000000000").as_bytes()), invalid("1000.500000000000000000"));
+    assert_eq!(ServiceBrc20Details.extract_response(details(19, "1000").as_bytes()),
+        Err(OrdError::InvalidAmountError{ field: "max supply".to_string(), amount: "21000000.000000000000000000".to_string() }));
+
+    // Malformed amounts
+    for mint_limit in ["", ".5", "1,000", "1e3", "-1000", "1000.0.0"] {
+        assert_eq!(ServiceBrc20Details.extract_response(details(18, mint_limit).as_bytes()), invalid(mint_limit));
+    }
+}
+
+#[test]
+fn test_amount_encoding() {
+
+    use crate::types::Brc20Amount;
+    use candid::Nat;
+
+    // The amount is exposed as a plain nat
+    let amount = Brc20Amount("1000500000000000000000".parse().unwrap());
+    let bytes = candid::encode_one(amount.clone()).unwrap();
+    assert_eq!(candid::decode_one::<Nat>(&bytes).unwrap(), amount.0);
+}
@@ -1,6 +1,7 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{parse_brc20_amount, BRC20_DECIMALS};
 
-use crate::{types::{Provider, HiroBrc20Holders, OrdResult, Brc20HoldersArgs}, utils::deserialize_response};
+use crate::{types::{Provider, HiroBrc20Holders, HiroBrc20HoldersJson, HiroBrc20Holder, OrdResult, Brc20HoldersArgs}, utils::deserialize_response};
 use std::ops::Add;
 
 pub struct ServiceBrc20Holders;
@@ -12,8 +13,7 @@ impl IsService for ServiceBrc20Holders {
             OrdFunction::Brc20Holders(Brc20HoldersArgs{ ticker, limit, offset }) => (ticker, limit, offset),
             _ => panic!("Invalid function: Brc20Holders expected"),
         };
-        BASE_URLS[&Provider::Hiro]
-            .clone()
+        base_url(Provider::Hiro)
             .add(
                 format!(
                     "/ordinals/v1/brc-20/tokens/{}/holders?offset={}&limit={}",
@@ -26,8 +26,21 @@ impl IsService for ServiceBrc20Holders {
     }
 
     fn extract_response(&self, bytes: &[u8]) -> OrdResult {
-        let brc20_details = deserialize_response::<HiroBrc20Holders>(bytes)?;
-        Ok(Response::Brc20Holders(brc20_details))
+        let holders = deserialize_response::<HiroBrc20HoldersJson>(bytes)?;
+        // The decimals of the token are unknown here, the ones of Hiro are assumed
+        let results = holders.results
+            .into_iter()
+            .map(|holder| Ok(HiroBrc20Holder {
+                overall_balance: parse_brc20_amount("overall balance", &holder.overall_balance, BRC20_DECIMALS)?,
+                address: holder.address,
+            }))
+            .collect::<Result<_, _>>()?;
+        Ok(Response::Brc20Holders(HiroBrc20Holders {
+            limit: holders.limit,
+            offset: holders.offset,
+            total: holders.total,
+            results,
+        }))
     }
 }
 
@@ -47,7 +60,7 @@ fn test_build_request() {
 #[test]
 fn test_extract_response() {
     
-    use crate::types::HiroBrc20Holder;
+    use crate::types::Brc20Amount;
 
     let bytes = r#"{
         "limit": 5,
@@ -85,23 +98,23 @@ fn test_extract_response() {
         results: vec![
             HiroBrc20Holder {
                 address: "bc1qqd72vtqlw0nugqmzrx398x8gj03z8aqr79aexrncezqaw74dtu4qxjydq3".to_string(),
-                overall_balance: "989780.514209670000000000".to_string(),
+                overall_balance: Brc20Amount("989780514209670000000000".parse().unwrap()),
             },
             HiroBrc20Holder {
                 address: "bc1qz7rw2atrt3e8jrywva2y8xmka8lewalx8qazlxaq8xkn2xke0yyqvpel3e".to_string(),
-                overall_balance: "650111.636402850000000000".to_string(),
+                overall_balance: Brc20Amount("650111636402850000000000".parse().unwrap()),
             },
             HiroBrc20Holder {
                 address: "bc1q8u9thhxvkjw9t8tf0sj6k0vwmk7jstc9z0f3at0r5xunxxp9f0pqmetg7x".to_string(),
-                overall_balance: "612586.442638590000000000".to_string(),
+                overall_balance: Brc20Amount("612586442638590000000000".parse().unwrap()),
             },
             HiroBrc20Holder {
                 address: "bc1qnw79hhts8r84gykqkctyhu3j4gckll9gqxktzqgx5a54m347zf7qxhcyn8".to_string(),
-                overall_balance: "509725.062426550000000000".to_string(),
+                overall_balance: Brc20Amount("509725062426550000000000".parse().unwrap()),
             },
             HiroBrc20Holder {
                 address: "bc1qm07w8kvcyst7wtv3spnxj07gnxy9cxffmzsczl9vsnzxu54cx90s90knnz".to_string(),
-                overall_balance: "409034.000000000000000000".to_string(),
+                overall_balance: Brc20Amount("409034000000000000000000".parse().unwrap()),
             },
         ],
     }));
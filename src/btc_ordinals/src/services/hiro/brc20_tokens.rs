@@ -0,0 +1,122 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::map_brc20_token;
+
+use crate::{http::QueryString, types::{Provider, HiroBrc20Tokens, HiroBrc20TokensJson, OrdResult, Brc20TokensArgs, Brc20TokensOrderBy}, utils::deserialize_response};
+use std::ops::Add;
+
+pub struct ServiceBrc20Tokens;
+
+impl IsService for ServiceBrc20Tokens {
+
+    fn get_url(&self, args: Args) -> String {
+        let (ticker_prefix, order_by, offset, limit) = match args.function {
+            OrdFunction::Brc20Tokens(Brc20TokensArgs{ ticker_prefix, order_by, offset, limit }) => (ticker_prefix, order_by, offset, limit),
+            _ => panic!("Invalid function: Brc20Tokens expected"),
+        };
+        // Hiro matches the tickers by prefix
+        let query = QueryString::new()
+            .list_param("ticker", &ticker_prefix.unwrap_or_default())
+            .opt_param("order_by", order_by.as_ref().map(order_by_param))
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add("/ordinals/v1/brc-20/tokens")
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let tokens = deserialize_response::<HiroBrc20TokensJson>(bytes)?;
+        let results = tokens.results
+            .into_iter()
+            .map(map_brc20_token)
+            .collect::<Result<_, _>>()?;
+        Ok(Response::Brc20Tokens(HiroBrc20Tokens {
+            limit: tokens.limit,
+            offset: tokens.offset,
+            total: tokens.total,
+            results,
+        }))
+    }
+}
+
+fn order_by_param(order_by: &Brc20TokensOrderBy) -> &'static str {
+    match order_by {
+        Brc20TokensOrderBy::DeployTimestamp => "index",
+        Brc20TokensOrderBy::TxCount         => "tx_count",
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    let service = ServiceBrc20Tokens;
+    let args = Args {
+        function: OrdFunction::Brc20Tokens(Brc20TokensArgs{ ticker_prefix: None, order_by: None, offset: 0, limit: 20 }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/brc-20/tokens?offset=0&limit=20");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+
+    let args = Args {
+        function: OrdFunction::Brc20Tokens(Brc20TokensArgs{
+            ticker_prefix: Some(vec!["or".to_string(), "sa".to_string()]),
+            order_by: Some(Brc20TokensOrderBy::TxCount),
+            offset: 20,
+            limit: 20,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args), "https://api.hiro.so/ordinals/v1/brc-20/tokens?ticker=or&ticker=sa&order_by=tx_count&offset=20&limit=20");
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::{HiroBrc20Token, Brc20Amount};
+
+    let bytes = r#"{
+        "limit": 1,
+        "offset": 0,
+        "total": 1,
+        "results": [
+          {
+            "id": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0",
+            "number": 348020,
+            "block_height": 779832,
+            "tx_id": "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735",
+            "address": "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06",
+            "ticker": "ordi",
+            "max_supply": "21000000.000000000000000000",
+            "mint_limit": "1000.000000000000000000",
+            "decimals": 18,
+            "deploy_timestamp": 1678248991000,
+            "minted_supply": "21000000.000000000000000000",
+            "tx_count": 225407
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceBrc20Tokens.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::Brc20Tokens(HiroBrc20Tokens {
+        limit: 1,
+        offset: 0,
+        total: 1,
+        results: vec![
+            HiroBrc20Token {
+                id: "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0".to_string(),
+                number: 348020,
+                block_height: 779832,
+                tx_id: "b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735".to_string(),
+                address: "bc1pxaneaf3w4d27hl2y93fuft2xk6m4u3wc4rafevc6slgd7f5tq2dqyfgy06".to_string(),
+                ticker: "ordi".to_string(),
+                max_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+                mint_limit: Brc20Amount("1000000000000000000000".parse().unwrap()),
+                decimals: 18,
+                deploy_timestamp: 1678248991000,
+                minted_supply: Brc20Amount("21000000000000000000000000".parse().unwrap()),
+                tx_count: 225407,
+            },
+        ],
+    }));
+}
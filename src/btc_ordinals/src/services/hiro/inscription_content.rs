@@ -1,4 +1,4 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
 
 use crate::types::{Provider, OrdResult, InscriptionContentArgs};
 use std::ops::Add;
@@ -12,8 +12,7 @@ impl IsService for ServiceHiroInscriptionContent {
             OrdFunction::InscriptionContent(InscriptionContentArgs{ inscription_id }) => inscription_id,
             _ => panic!("Invalid function: InscriptionContent expected"),
         };
-        BASE_URLS[&Provider::Hiro]
-            .clone()
+        base_url(Provider::Hiro)
             .add(
                 format!(
                     "/ordinals/v1/inscriptions/{}/content",
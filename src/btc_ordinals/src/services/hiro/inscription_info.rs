@@ -1,6 +1,7 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::map_inscription;
 
-use crate::{types::{Provider, HiroSatInscription, OrdResult, InscriptionInfoArgs}, utils::deserialize_response};
+use crate::{types::{Provider, HiroSatInscription, OrdResult, OrdError, InscriptionInfoArgs}, utils::deserialize_response};
 use std::ops::Add;
 
 pub struct ServiceHiroInscriptionInfo;
@@ -12,8 +13,7 @@ impl IsService for ServiceHiroInscriptionInfo {
             OrdFunction::InscriptionInfo(InscriptionInfoArgs{ inscription_id }) => inscription_id,
             _ => panic!("Invalid function: InscriptionInfo expected"),
         };
-        BASE_URLS[&Provider::Hiro]
-            .clone()
+        base_url(Provider::Hiro)
             .add(
                 format!(
                     "/ordinals/v1/inscriptions/{}",
@@ -24,8 +24,15 @@ impl IsService for ServiceHiroInscriptionInfo {
     }
 
     fn extract_response(&self, bytes: &[u8]) -> OrdResult {
-        let sat_inscriptions = deserialize_response::<HiroSatInscription>(bytes)?;
-        Ok(Response::InscriptionInfo(sat_inscriptions))
+        let inscription = deserialize_response::<HiroSatInscription>(bytes)?;
+        Ok(Response::HiroInscriptionInfo(inscription))
+    }
+
+    fn common_response(&self, response: Response) -> Result<Response, OrdError> {
+        match response {
+            Response::HiroInscriptionInfo(inscription) => Ok(Response::InscriptionInfo(map_inscription(inscription)?)),
+            response => Ok(response),
+        }
     }
 }
 
@@ -43,8 +50,8 @@ fn test_build_request() {
 
 #[test]
 fn test_extract_response() {
-    
-    use crate::types::HiroSatInscription;
+
+    use crate::types::InscriptionInfo;
 
     let bytes = r#"{
         "id": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0",
@@ -73,31 +80,31 @@ fn test_extract_response() {
         "recursion_refs": null
       }"#.as_bytes();
     
+    // The full Hiro inscription is returned by hiro_inscription_info
     let response = ServiceHiroInscriptionInfo.extract_response(bytes).unwrap();
-    assert_eq!(response, Response::InscriptionInfo(HiroSatInscription {
+    match &response {
+        Response::HiroInscriptionInfo(inscription) => {
+            assert_eq!(inscription.genesis_address, "bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td");
+            assert_eq!(inscription.sat_rarity, "common");
+            assert_eq!(inscription.mime_type, "image/png");
+        },
+        _ => panic!("Unexpected response type"),
+    }
+
+    // The request method compares the fields common to all the providers
+    let response = ServiceHiroInscriptionInfo.common_response(response).unwrap();
+    assert_eq!(response, Response::InscriptionInfo(InscriptionInfo {
         id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string(),
         number: 19095,
-        address: "bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td".to_string(),
-        genesis_address: "bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td".to_string(),
-        genesis_block_height: 775617,
-        genesis_block_hash: "00000000000000000003e4523d5f3008bbf4deeaf8b6acca345241bfa9097d75".to_string(),
-        genesis_tx_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc".to_string(),
-        genesis_fee: "2805".to_string(),
-        genesis_timestamp: 1675884508000,
-        tx_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc".to_string(),
+        address: Some("bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td".to_string()),
+        genesis_height: 775617,
+        genesis_fee: 2805,
+        genesis_timestamp: 1675884508,
         location: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0".to_string(),
         output: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0".to_string(),
-        value: "10000".to_string(),
-        offset: "0".to_string(),
-        sat_ordinal: "257418248345364".to_string(),
-        sat_rarity: "common".to_string(),
-        sat_coinbase_height: 51483,
-        mime_type: "image/png".to_string(),
-        content_type: "image/png".to_string(),
-        content_length: 208,
-        timestamp: 1675884508000,
-        curse_type: None,
-        recursive: false,
-        recursion_refs: None,
+        value: Some(10000),
+        sat: Some(257418248345364),
+        content_type: Some("image/png".to_string()),
+        content_length: Some(208),
     }));
-}
\ No newline at end of file
+}
@@ -0,0 +1,143 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::map_transfer;
+
+use crate::{http::QueryString, types::{Provider, HiroInscriptionTransfers, InscriptionTransfers, OrdResult, InscriptionTransfersArgs}, utils::deserialize_response};
+use std::ops::Add;
+
+pub struct ServiceHiroInscriptionTransfers;
+
+impl IsService for ServiceHiroInscriptionTransfers {
+
+    fn get_url(&self, args: Args) -> String {
+        let (inscription_id, offset, limit) = match args.function {
+            OrdFunction::InscriptionTransfers(InscriptionTransfersArgs{ inscription_id, offset, limit }) => (inscription_id, offset, limit),
+            _ => panic!("Invalid function: InscriptionTransfers expected"),
+        };
+        let query = QueryString::new()
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add(format!("/ordinals/v1/inscriptions/{}/transfers", inscription_id).as_str())
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let transfers = deserialize_response::<HiroInscriptionTransfers>(bytes)?;
+        let results = transfers.results
+            .into_iter()
+            .map(map_transfer)
+            .collect::<Result<_, _>>()?;
+        Ok(Response::InscriptionTransfers(InscriptionTransfers {
+            limit: transfers.limit,
+            offset: transfers.offset,
+            total: transfers.total,
+            results,
+        }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+    let service = ServiceHiroInscriptionTransfers;
+    let args = Args {
+        function: OrdFunction::InscriptionTransfers(InscriptionTransfersArgs{ inscription_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string(), offset: 0, limit: 10 }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/inscriptions/38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0/transfers?offset=0&limit=10");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::InscriptionTransfer;
+
+    let bytes = r#"{
+        "limit": 2,
+        "offset": 0,
+        "total": 2,
+        "results": [
+          {
+            "block_height": 804113,
+            "block_hash": "00000000000000000001a0e7b3fb0a4c8b0f3f5c7f5b58b1c1e5a0b7e5a2e1f3",
+            "address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+            "tx_id": "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4",
+            "location": "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0:0",
+            "output": "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0",
+            "value": "546",
+            "offset": "0",
+            "timestamp": 1693616765000
+          },
+          {
+            "block_height": 775617,
+            "block_hash": "00000000000000000003e4523d5f3008bbf4deeaf8b6acca345241bfa9097d75",
+            "address": null,
+            "tx_id": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc",
+            "location": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0",
+            "output": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0",
+            "value": "10000",
+            "offset": "0",
+            "timestamp": 1675884508000
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceHiroInscriptionTransfers.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::InscriptionTransfers(InscriptionTransfers {
+        limit: 2,
+        offset: 0,
+        total: 2,
+        results: vec![
+            InscriptionTransfer {
+                block_height: 804113,
+                block_hash: "00000000000000000001a0e7b3fb0a4c8b0f3f5c7f5b58b1c1e5a0b7e5a2e1f3".to_string(),
+                address: Some("bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string()),
+                tx_id: "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4".to_string(),
+                location: "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0:0".to_string(),
+                output: "5b868359ab92e242c0be0cb1a12bd7bb5450a004d8137a462cd4d171ba10c6c4:0".to_string(),
+                value: 546,
+                offset: 0,
+                timestamp: 1693616765,
+            },
+            InscriptionTransfer {
+                block_height: 775617,
+                block_hash: "00000000000000000003e4523d5f3008bbf4deeaf8b6acca345241bfa9097d75".to_string(),
+                address: None,
+                tx_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc".to_string(),
+                location: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0".to_string(),
+                output: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0".to_string(),
+                value: 10000,
+                offset: 0,
+                timestamp: 1675884508,
+            },
+        ],
+    }));
+}
+
+#[test]
+fn test_invalid_value() {
+
+    use crate::types::OrdError;
+
+    let bytes = r#"{
+        "limit": 1,
+        "offset": 0,
+        "total": 1,
+        "results": [
+          {
+            "block_height": 775617,
+            "block_hash": "00000000000000000003e4523d5f3008bbf4deeaf8b6acca345241bfa9097d75",
+            "address": null,
+            "tx_id": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc",
+            "location": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0",
+            "output": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0",
+            "value": "10k",
+            "offset": "0",
+            "timestamp": 1675884508000
+          }
+        ]
+      }"#.as_bytes();
+
+    assert_eq!(ServiceHiroInscriptionTransfers.extract_response(bytes), Err(OrdError::ResponseDecodingError("Invalid value: 10k".to_string())));
+}
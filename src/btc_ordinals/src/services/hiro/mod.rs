@@ -1,6 +1,152 @@
+pub mod address_inscriptions;
+pub mod block_inscription_activity;
+pub mod block_transfers;
+pub mod brc20_activity;
+pub mod brc20_balances;
 pub mod brc20_details;
 pub mod brc20_holders;
+pub mod brc20_tokens;
 pub mod inscription_content;
 pub mod inscription_info;
+pub mod inscription_transfers;
+pub mod rune_activity;
+pub mod rune_balances;
+pub mod rune_details;
+pub mod rune_holders;
 pub mod sat_info;
-pub mod sat_inscriptions;
\ No newline at end of file
+pub mod sat_inscriptions;
+pub mod search_inscriptions;
+
+use candid::Nat;
+
+use crate::utils::{normalize_rune_name, normalize_spaced_rune_name};
+use crate::types::{BlockId, RuneId, HiroInscriptionTransfer, HiroSatInscription, InscriptionInfo, InscriptionTransfer, OrdError,
+    Brc20Amount, HiroBrc20Token, HiroBrc20TokenJson, RuneAmount, HiroRune};
+
+// Hiro gives the amounts as strings
+fn parse_amount(field: &str, amount: &str) -> Result<u64, OrdError> {
+    amount.parse().map_err(|_| OrdError::ResponseDecodingError(format!("Invalid {}: {}", field, amount)))
+}
+
+// The number of decimals of the BRC-20 amounts given by Hiro, the maximum allowed for a token
+const BRC20_DECIMALS: u64 = 18;
+
+// Hiro gives the BRC-20 amounts as decimal strings (e.g. "1000.000000000000000000"), they are
+// turned into integers scaled by 10^18 so that they compare exactly. An amount is rejected when
+// it is not a decimal number, or when it has more significant decimals than the token allows.
+fn parse_brc20_amount(field: &str, amount: &str, decimals: u64) -> Result<Brc20Amount, OrdError> {
+    if decimals > BRC20_DECIMALS {
+        return Err(OrdError::InvalidAmountError{ field: field.to_string(), amount: amount.to_string() });
+    }
+    parse_decimal(field, amount, decimals as usize, BRC20_DECIMALS as usize).map(Brc20Amount)
+}
+
+// The maximum divisibility of a rune
+const MAX_RUNE_DIVISIBILITY: usize = 38;
+
+// Hiro gives the rune amounts as decimal strings too (e.g. "1000.00000" for a divisibility of 5).
+// They are turned into integers in the smallest unit of the rune, hence only when the divisibility
+// is part of the response (details): the holders, balances and activity keep the decimal strings.
+fn parse_rune_amount(field: &str, amount: &str, divisibility: u8) -> Result<RuneAmount, OrdError> {
+    let divisibility = divisibility as usize;
+    if divisibility > MAX_RUNE_DIVISIBILITY {
+        return Err(OrdError::InvalidAmountError{ field: field.to_string(), amount: amount.to_string() });
+    }
+    parse_decimal(field, amount, divisibility, divisibility).map(RuneAmount)
+}
+
+// Hiro gives the counts as strings, they can exceed 64 bits (e.g. the cap of a rune)
+fn parse_count(field: &str, count: &str) -> Result<Nat, OrdError> {
+    parse_decimal(field, count, 0, 0)
+}
+
+// Turns the decimal string into an integer scaled by 10^scale. The amount is rejected when it is
+// not a decimal number, or when it has more significant decimals than allowed.
+fn parse_decimal(field: &str, amount: &str, decimals: usize, scale: usize) -> Result<Nat, OrdError> {
+    let invalid = || OrdError::InvalidAmountError{ field: field.to_string(), amount: amount.to_string() };
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+    if fraction.trim_end_matches('0').len() > decimals {
+        return Err(invalid());
+    }
+    let fraction = &fraction[..fraction.len().min(scale)];
+    format!("{}{:0<width$}", integer, fraction, width = scale)
+        .parse::<Nat>()
+        .map_err(|_| invalid())
+}
+
+// The names are normalized, so that the providers agree whatever their spacers or case
+fn map_rune(rune: HiroRune) -> HiroRune {
+    HiroRune {
+        name: normalize_rune_name(&rune.name),
+        spaced_name: normalize_spaced_rune_name(&rune.spaced_name),
+        ..rune
+    }
+}
+
+fn map_brc20_token(token: HiroBrc20TokenJson) -> Result<HiroBrc20Token, OrdError> {
+    Ok(HiroBrc20Token {
+        max_supply: parse_brc20_amount("max supply", &token.max_supply, token.decimals)?,
+        mint_limit: parse_brc20_amount("mint limit", &token.mint_limit, token.decimals)?,
+        minted_supply: parse_brc20_amount("minted supply", &token.minted_supply, token.decimals)?,
+        id: token.id,
+        number: token.number,
+        block_height: token.block_height,
+        tx_id: token.tx_id,
+        address: token.address,
+        ticker: token.ticker,
+        decimals: token.decimals,
+        deploy_timestamp: token.deploy_timestamp,
+        tx_count: token.tx_count,
+    })
+}
+
+// Hiro accepts either the height or the hash of a block
+fn block_id_param(block: &BlockId) -> String {
+    match block {
+        BlockId::Height(height) => height.to_string(),
+        BlockId::Hash(hash)     => hash.clone(),
+    }
+}
+
+// Hiro accepts either the name, with or without spacers, or the id of a rune
+fn rune_id_param(rune: &RuneId) -> String {
+    match rune {
+        RuneId::Name(name)       => normalize_rune_name(name),
+        RuneId::Id { block, tx }  => format!("{}:{}", block, tx),
+    }
+}
+
+fn map_inscription(inscription: HiroSatInscription) -> Result<InscriptionInfo, OrdError> {
+    Ok(InscriptionInfo {
+        id: inscription.id,
+        number: inscription.number,
+        address: Some(inscription.address),
+        genesis_height: inscription.genesis_block_height,
+        genesis_fee: parse_amount("genesis fee", &inscription.genesis_fee)?,
+        genesis_timestamp: inscription.genesis_timestamp / 1000, // Hiro gives milliseconds
+        location: inscription.location,
+        output: inscription.output,
+        value: Some(parse_amount("value", &inscription.value)?),
+        sat: Some(parse_amount("sat", &inscription.sat_ordinal)?),
+        content_type: Some(inscription.content_type),
+        content_length: Some(inscription.content_length),
+    })
+}
+
+fn map_transfer(transfer: HiroInscriptionTransfer) -> Result<InscriptionTransfer, OrdError> {
+    Ok(InscriptionTransfer {
+        block_height: transfer.block_height,
+        block_hash: transfer.block_hash,
+        address: transfer.address,
+        tx_id: transfer.tx_id,
+        location: transfer.location,
+        output: transfer.output,
+        value: parse_amount("value", &transfer.value)?,
+        offset: parse_amount("offset", &transfer.offset)?,
+        timestamp: transfer.timestamp / 1000, // Hiro gives milliseconds
+    })
+}
@@ -0,0 +1,133 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{rune_id_param, map_rune};
+
+use crate::{http::QueryString, types::{Provider, HiroRuneActivity, HiroRuneActivityEntry, OrdResult, RuneActivityArgs},
+    utils::{deserialize_response, normalize_address}};
+use std::ops::Add;
+
+pub struct ServiceRuneActivity;
+
+impl IsService for ServiceRuneActivity {
+
+    fn get_url(&self, args: Args) -> String {
+        let (rune, address, offset, limit) = match args.function {
+            OrdFunction::RuneActivity(RuneActivityArgs{ rune, address, offset, limit }) => (rune, address, offset, limit),
+            _ => panic!("Invalid function: RuneActivity expected"),
+        };
+        let path = match (rune, address) {
+            (Some(rune), Some(address)) => format!("/runes/v1/etchings/{}/activity/{}", rune_id_param(&rune), normalize_address(&address)),
+            (Some(rune), None)          => format!("/runes/v1/etchings/{}/activity", rune_id_param(&rune)),
+            (None, Some(address))       => format!("/runes/v1/addresses/{}/activity", normalize_address(&address)),
+            (None, None)                => panic!("Invalid function: RuneActivity requires a rune or an address"),
+        };
+        let query = QueryString::new()
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add(path.as_str())
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let activity = deserialize_response::<HiroRuneActivity>(bytes)?;
+        let results = activity.results
+            .into_iter()
+            .map(|entry| HiroRuneActivityEntry { rune: map_rune(entry.rune), ..entry })
+            .collect();
+        Ok(Response::RuneActivity(HiroRuneActivity { results, ..activity }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    use crate::types::RuneId;
+
+    let service = ServiceRuneActivity;
+    let activity = |rune: Option<RuneId>, address: Option<&str>| Args {
+        function: OrdFunction::RuneActivity(RuneActivityArgs{ rune, address: address.map(str::to_string), offset: 0, limit: 20 }),
+        max_kb_per_item: None,
+    };
+    let address = "bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz";
+
+    assert_eq!(service.get_url(activity(Some(RuneId::Id{ block: 840000, tx: 3 }), None)), "https://api.hiro.so/runes/v1/etchings/840000:3/activity?offset=0&limit=20");
+    assert_eq!(service.get_url(activity(None, Some(address))), "https://api.hiro.so/runes/v1/addresses/bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz/activity?offset=0&limit=20");
+    assert_eq!(service.get_url(activity(Some(RuneId::Name("DOG•GO•TO•THE•MOON".to_string())), Some(address))),
+        "https://api.hiro.so/runes/v1/etchings/DOGGOTOTHEMOON/activity/bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz?offset=0&limit=20");
+    assert_eq!(service.get_body(activity(None, Some(address))), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::{HiroRune, HiroRuneLocation};
+
+    let bytes = r#"{
+        "limit": 1,
+        "offset": 0,
+        "total": 1,
+        "results": [
+          {
+            "rune": {
+              "id": "840000:3",
+              "number": 3,
+              "name": "DOGGOTOTHEMOON",
+              "spaced_name": "DOG•GO•TO•THE•MOON"
+            },
+            "operation": "send",
+            "address": "bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz",
+            "receiver_address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+            "amount": "1000.00000",
+            "location": {
+              "block_hash": "00000000000000000000c9787573a1f1775a2b56b403a2d0c7957e9a5bc754bb",
+              "block_height": 840010,
+              "tx_id": "3c2f2d9bd46c6dbb8d4ae6c23f2d7a7cb0dd0bca0f3ea2e7ff9d8d66dd8f9fd0",
+              "tx_index": 12,
+              "vout": 1,
+              "output": "3c2f2d9bd46c6dbb8d4ae6c23f2d7a7cb0dd0bca0f3ea2e7ff9d8d66dd8f9fd0:1",
+              "timestamp": 1713577654
+            }
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceRuneActivity.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::RuneActivity(HiroRuneActivity {
+        limit: 1,
+        offset: 0,
+        total: 1,
+        results: vec![
+            HiroRuneActivityEntry {
+                rune: HiroRune {
+                    id: "840000:3".to_string(),
+                    number: 3,
+                    name: "DOGGOTOTHEMOON".to_string(),
+                    spaced_name: "DOG•GO•TO•THE•MOON".to_string(),
+                },
+                operation: "send".to_string(),
+                address: Some("bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz".to_string()),
+                receiver_address: Some("bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string()),
+                amount: Some("1000.00000".to_string()),
+                location: HiroRuneLocation {
+                    block_hash: "00000000000000000000c9787573a1f1775a2b56b403a2d0c7957e9a5bc754bb".to_string(),
+                    block_height: 840010,
+                    tx_id: "3c2f2d9bd46c6dbb8d4ae6c23f2d7a7cb0dd0bca0f3ea2e7ff9d8d66dd8f9fd0".to_string(),
+                    tx_index: 12,
+                    vout: Some(1),
+                    output: Some("3c2f2d9bd46c6dbb8d4ae6c23f2d7a7cb0dd0bca0f3ea2e7ff9d8d66dd8f9fd0:1".to_string()),
+                    timestamp: 1713577654,
+                },
+            },
+        ],
+    }));
+}
+
+#[test]
+fn test_validate_args() {
+
+    use crate::{types::OrdError, utils::validate_function};
+
+    let activity = OrdFunction::RuneActivity(RuneActivityArgs{ rune: None, address: None, offset: 0, limit: 20 });
+    assert_eq!(validate_function(&activity), Err(OrdError::InvalidArgsError("Either a rune or an address is required".to_string())));
+}
@@ -0,0 +1,94 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+
+use super::map_rune;
+
+use crate::{http::QueryString, types::{Provider, HiroRuneBalances, HiroRuneBalance, OrdResult, RuneBalancesArgs},
+    utils::{deserialize_response, normalize_address}};
+use std::ops::Add;
+
+pub struct ServiceRuneBalances;
+
+impl IsService for ServiceRuneBalances {
+
+    fn get_url(&self, args: Args) -> String {
+        let (address, offset, limit) = match args.function {
+            OrdFunction::RuneBalances(RuneBalancesArgs{ address, offset, limit }) => (address, offset, limit),
+            _ => panic!("Invalid function: RuneBalances expected"),
+        };
+        let query = QueryString::new()
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add(format!("/runes/v1/addresses/{}/balances", normalize_address(&address)).as_str())
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let balances = deserialize_response::<HiroRuneBalances>(bytes)?;
+        let results = balances.results
+            .into_iter()
+            .map(|balance| HiroRuneBalance { rune: map_rune(balance.rune), ..balance })
+            .collect();
+        Ok(Response::RuneBalances(HiroRuneBalances { results, ..balances }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    let service = ServiceRuneBalances;
+    let args = Args {
+        function: OrdFunction::RuneBalances(RuneBalancesArgs{
+            address: "bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz".to_string(),
+            offset: 0,
+            limit: 20,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/runes/v1/addresses/bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz/balances?offset=0&limit=20");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::HiroRune;
+
+    let bytes = r#"{
+        "limit": 1,
+        "offset": 0,
+        "total": 1,
+        "results": [
+          {
+            "rune": {
+              "id": "840000:3",
+              "number": 3,
+              "name": "DOGGOTOTHEMOON",
+              "spaced_name": "DOG•GO•TO•THE•MOON"
+            },
+            "address": "bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz",
+            "balance": "3250000000.00000"
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceRuneBalances.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::RuneBalances(HiroRuneBalances {
+        limit: 1,
+        offset: 0,
+        total: 1,
+        results: vec![
+            HiroRuneBalance {
+                rune: HiroRune {
+                    id: "840000:3".to_string(),
+                    number: 3,
+                    name: "DOGGOTOTHEMOON".to_string(),
+                    spaced_name: "DOG•GO•TO•THE•MOON".to_string(),
+                },
+                address: Some("bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz".to_string()),
+                balance: "3250000000.00000".to_string(),
+            },
+        ],
+    }));
+}
@@ -0,0 +1,227 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::{rune_id_param, parse_rune_amount, parse_count};
+
+use crate::{types::{Provider, HiroRuneDetails, HiroRuneDetailsJson, HiroRuneMintTerms, HiroRuneSupply, OrdResult, RuneDetailsArgs},
+    utils::{deserialize_response, normalize_rune_name, normalize_spaced_rune_name}};
+use std::ops::Add;
+
+pub struct ServiceRuneDetails;
+
+impl IsService for ServiceRuneDetails {
+
+    fn get_url(&self, args: Args) -> String {
+        let rune = match args.function {
+            OrdFunction::RuneDetails(RuneDetailsArgs{ rune }) => rune,
+            _ => panic!("Invalid function: RuneDetails expected"),
+        };
+        base_url(Provider::Hiro)
+            .add(format!("/runes/v1/etchings/{}", rune_id_param(&rune)).as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let rune = deserialize_response::<HiroRuneDetailsJson>(bytes)?;
+        let amount = |field: &str, amount: &str| parse_rune_amount(field, amount, rune.divisibility);
+        Ok(Response::RuneDetails(HiroRuneDetails {
+            mint_terms: HiroRuneMintTerms {
+                amount: rune.mint_terms.amount.as_deref().map(|mint| amount("mint amount", mint)).transpose()?,
+                cap: rune.mint_terms.cap.as_deref().map(|cap| parse_count("mint cap", cap)).transpose()?,
+                height_start: rune.mint_terms.height_start,
+                height_end: rune.mint_terms.height_end,
+                offset_start: rune.mint_terms.offset_start,
+                offset_end: rune.mint_terms.offset_end,
+            },
+            supply: HiroRuneSupply {
+                current: amount("current supply", &rune.supply.current)?,
+                minted: amount("minted supply", &rune.supply.minted)?,
+                total_mints: parse_count("total mints", &rune.supply.total_mints)?,
+                mint_percentage: rune.supply.mint_percentage,
+                mintable: rune.supply.mintable,
+                burned: amount("burned supply", &rune.supply.burned)?,
+                total_burns: parse_count("total burns", &rune.supply.total_burns)?,
+                premine: amount("premine", &rune.supply.premine)?,
+            },
+            id: rune.id,
+            name: normalize_rune_name(&rune.name),
+            spaced_name: normalize_spaced_rune_name(&rune.spaced_name),
+            number: rune.number,
+            divisibility: rune.divisibility,
+            symbol: rune.symbol,
+            turbo: rune.turbo,
+            location: rune.location,
+        }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    use crate::types::RuneId;
+
+    let service = ServiceRuneDetails;
+    let args = Args {
+        function: OrdFunction::RuneDetails(RuneDetailsArgs{ rune: RuneId::Name("uncommon•goods".to_string()) }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/runes/v1/etchings/UNCOMMONGOODS");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+
+    let args = Args {
+        function: OrdFunction::RuneDetails(RuneDetailsArgs{ rune: RuneId::Id{ block: 840000, tx: 1 } }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args), "https://api.hiro.so/runes/v1/etchings/840000:1");
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::{HiroRuneLocation, RuneAmount};
+
+    let bytes = r#"{
+        "id": "1:0",
+        "name": "UNCOMMONGOODS",
+        "spaced_name": "UNCOMMON•GOODS",
+        "number": 0,
+        "divisibility": 0,
+        "symbol": "⧉",
+        "turbo": true,
+        "mint_terms": {
+          "amount": "1",
+          "cap": "340282366920938463463374607431768211455",
+          "height_start": 840000,
+          "height_end": 1050000,
+          "offset_start": null,
+          "offset_end": null
+        },
+        "supply": {
+          "current": "84217537",
+          "minted": "84217537",
+          "total_mints": "84217537",
+          "mint_percentage": "0.0000",
+          "mintable": true,
+          "burned": "5307",
+          "total_burns": "5276",
+          "premine": "0"
+        },
+        "location": {
+          "block_hash": "0000000000000000000320283a032748cef8227873ff4872689bf23f1cda83a5",
+          "block_height": 1,
+          "tx_id": "0000000000000000000000000000000000000000000000000000000000000000",
+          "tx_index": 0,
+          "vout": null,
+          "output": null,
+          "timestamp": 1713571767
+        }
+      }"#.as_bytes();
+
+    let response = ServiceRuneDetails.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::RuneDetails(HiroRuneDetails {
+        id: "1:0".to_string(),
+        name: "UNCOMMONGOODS".to_string(),
+        spaced_name: "UNCOMMON•GOODS".to_string(),
+        number: 0,
+        divisibility: 0,
+        symbol: "⧉".to_string(),
+        turbo: true,
+        mint_terms: HiroRuneMintTerms {
+            amount: Some(RuneAmount(1u64.into())),
+            cap: Some("340282366920938463463374607431768211455".parse().unwrap()),
+            height_start: Some(840000),
+            height_end: Some(1050000),
+            offset_start: None,
+            offset_end: None,
+        },
+        supply: HiroRuneSupply {
+            current: RuneAmount(84217537u64.into()),
+            minted: RuneAmount(84217537u64.into()),
+            total_mints: 84217537u64.into(),
+            mint_percentage: "0.0000".to_string(),
+            mintable: true,
+            burned: RuneAmount(5307u64.into()),
+            total_burns: 5276u64.into(),
+            premine: RuneAmount(0u64.into()),
+        },
+        location: HiroRuneLocation {
+            block_hash: "0000000000000000000320283a032748cef8227873ff4872689bf23f1cda83a5".to_string(),
+            block_height: 1,
+            tx_id: "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+            tx_index: 0,
+            vout: None,
+            output: None,
+            timestamp: 1713571767,
+        },
+    }));
+}
+
+#[test]
+fn test_extract_divisible_rune() {
+
+    use crate::types::{OrdError, RuneAmount};
+
+    let details = |premine: &str| format!(r#"{{
+        "id": "840000:3",
+        "name": "doggotothemoon",
+        "spaced_name": "dog.go.to.the.moon",
+        "number": 3,
+        "divisibility": 5,
+        "symbol": "🐕",
+        "turbo": true,
+        "mint_terms": {{
+          "amount": null,
+          "cap": null,
+          "height_start": null,
+          "height_end": null,
+          "offset_start": null,
+          "offset_end": null
+        }},
+        "supply": {{
+          "current": "100000000000.00000",
+          "minted": "0.00000",
+          "total_mints": "0",
+          "mint_percentage": "100.0000",
+          "mintable": false,
+          "burned": "12.5",
+          "total_burns": "3",
+          "premine": "{}"
+        }},
+        "location": {{
+          "block_hash": "0000000000000000000320283a032748cef8227873ff4872689bf23f1cda83a5",
+          "block_height": 840000,
+          "tx_id": "e79134080a83fe3e0e06ed6990c5a9b63b362313341745707a2bff7d788a1375",
+          "tx_index": 3,
+          "vout": null,
+          "output": null,
+          "timestamp": 1713571767
+        }}
+      }}"#, premine);
+
+    // The amounts are scaled by the divisibility, the names are normalized
+    let rune = match ServiceRuneDetails.extract_response(details("100000000000.00000").as_bytes()).unwrap() {
+        Response::RuneDetails(rune) => rune,
+        _ => panic!("Unexpected response type"),
+    };
+    assert_eq!(rune.name, "DOGGOTOTHEMOON");
+    assert_eq!(rune.spaced_name, "DOG•GO•TO•THE•MOON");
+    assert_eq!(rune.supply.current, RuneAmount("10000000000000000".parse().unwrap()));
+    assert_eq!(rune.supply.minted, RuneAmount(0u64.into()));
+    assert_eq!(rune.supply.burned, RuneAmount(1250000u64.into()));
+    assert_eq!(rune.supply.premine, rune.supply.current);
+    assert_eq!(rune.mint_terms.amount, None);
+
+    // An amount with more decimals than the divisibility is rejected
+    assert_eq!(ServiceRuneDetails.extract_response(details("1.000001").as_bytes()),
+        Err(OrdError::InvalidAmountError{ field: "premine".to_string(), amount: "1.000001".to_string() }));
+}
+
+#[test]
+fn test_validate_rune() {
+
+    use crate::{types::{OrdError, RuneId}, utils::validate_function};
+
+    let details = |name: &str| OrdFunction::RuneDetails(RuneDetailsArgs{ rune: RuneId::Name(name.to_string()) });
+    assert_eq!(validate_function(&details("UNCOMMON•GOODS")), Ok(()));
+    assert_eq!(validate_function(&details("dog.go.to.the.moon")), Ok(()));
+    assert_eq!(validate_function(&details("••")), Err(OrdError::InvalidArgsError("Invalid rune name: ••".to_string())));
+    assert_eq!(validate_function(&details("DOG/../1")), Err(OrdError::InvalidArgsError("Invalid rune name: DOG/../1".to_string())));
+}
@@ -0,0 +1,123 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::rune_id_param;
+
+use crate::{http::QueryString, types::{Provider, HiroRuneHolders, OrdResult, RuneHoldersArgs}, utils::deserialize_response};
+use std::ops::Add;
+
+pub struct ServiceRuneHolders;
+
+impl IsService for ServiceRuneHolders {
+
+    fn get_url(&self, args: Args) -> String {
+        let (rune, offset, limit) = match args.function {
+            OrdFunction::RuneHolders(RuneHoldersArgs{ rune, offset, limit }) => (rune, offset, limit),
+            _ => panic!("Invalid function: RuneHolders expected"),
+        };
+        let query = QueryString::new()
+            .param("offset", offset)
+            .param("limit", limit);
+        base_url(Provider::Hiro)
+            .add(format!("/runes/v1/etchings/{}/holders", rune_id_param(&rune)).as_str())
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let holders = deserialize_response::<HiroRuneHolders>(bytes)?;
+        Ok(Response::RuneHolders(holders))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    use crate::types::RuneId;
+
+    let service = ServiceRuneHolders;
+    let args = Args {
+        function: OrdFunction::RuneHolders(RuneHoldersArgs{ rune: RuneId::Name("DOG•GO•TO•THE•MOON".to_string()), offset: 2, limit: 5 }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/runes/v1/etchings/DOGGOTOTHEMOON/holders?offset=2&limit=5");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::HiroRuneHolder;
+
+    let bytes = r#"{
+        "limit": 2,
+        "offset": 0,
+        "total": 75386,
+        "results": [
+          {
+            "address": "bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz",
+            "balance": "3250000000.00000"
+          },
+          {
+            "address": null,
+            "balance": "1.00000"
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceRuneHolders.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::RuneHolders(HiroRuneHolders {
+        limit: 2,
+        offset: 0,
+        total: 75386,
+        results: vec![
+            HiroRuneHolder {
+                address: Some("bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz".to_string()),
+                balance: "3250000000.00000".to_string(),
+            },
+            HiroRuneHolder {
+                address: None,
+                balance: "1.00000".to_string(),
+            },
+        ],
+    }));
+}
+
+#[test]
+fn test_extract_decimal_lengths() {
+
+    use crate::types::HiroRuneHolder;
+
+    // The divisibility of the rune is not part of the response, hence the balances are returned as
+    // given: the same rune written with different decimal lengths is never scaled into different units
+    let bytes = r#"{
+        "limit": 2,
+        "offset": 0,
+        "total": 2,
+        "results": [
+          {
+            "address": "bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz",
+            "balance": "1000"
+          },
+          {
+            "address": "bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse",
+            "balance": "1000.00000"
+          }
+        ]
+      }"#.as_bytes();
+
+    let response = ServiceRuneHolders.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::RuneHolders(HiroRuneHolders {
+        limit: 2,
+        offset: 0,
+        total: 2,
+        results: vec![
+            HiroRuneHolder {
+                address: Some("bc1pnmzyt4gekz2g0dhcs2vay7zagz3zmqqlt4ndkpsfvytqhcuhd8dq3ug8hz".to_string()),
+                balance: "1000".to_string(),
+            },
+            HiroRuneHolder {
+                address: Some("bc1pkctwmtz5llxuu466htg2dppj4lm29wnx28n2cxwwwm9xquzres9qqshvse".to_string()),
+                balance: "1000.00000".to_string(),
+            },
+        ],
+    }));
+}
@@ -1,6 +1,6 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
 
-use crate::{types::{Provider, HiroSatInfo, SatInfo, OrdResult, OrdError, SatInfoArgs}, utils::{map_str_rarity, deserialize_response}};
+use crate::{ordinals::Sat, types::{Provider, HiroSatInfo, SatInfo, OrdResult, OrdError, SatInfoArgs}, utils::{map_str_rarity, deserialize_response}};
 
 use std::ops::Add;
 
@@ -10,11 +10,10 @@ impl IsService for ServiceHiroSatInfo {
 
     fn get_url(&self, args: Args) -> String {
         let ordinal = match args.function {
-            OrdFunction::SatInfo(SatInfoArgs{ ordinal }) => ordinal,
+            OrdFunction::SatInfo(SatInfoArgs{ sat }) => sat.parse().expect("Invalid sat notation"),
             _ => panic!("Invalid function: SatInfo expected"),
         };
-        BASE_URLS[&Provider::Hiro]
-            .clone()
+        base_url(Provider::Hiro)
             .add(
                 format!("/ordinals/v1/sats/{}", ordinal)
                     .as_str()
@@ -25,12 +24,16 @@ impl IsService for ServiceHiroSatInfo {
         let hiro_sat_info = deserialize_response::<HiroSatInfo>(bytes)?;
         let rarity = map_str_rarity(&hiro_sat_info.rarity)
             .ok_or(OrdError::ResponseDecodingError(format!("Invalid rarity: {}", hiro_sat_info.rarity)))?;
+        // Hiro only gives the rarity, the other satributes are deduced from the sat
+        let sat = Sat::from_height_and_offset(hiro_sat_info.coinbase_height, hiro_sat_info.offset)
+            .ok_or(OrdError::ResponseDecodingError(format!("Invalid sat: {}", hiro_sat_info.decimal)))?;
         Ok(Response::SatInfo(SatInfo {
             height: hiro_sat_info.coinbase_height,
             cycle: hiro_sat_info.cycle,
             epoch: hiro_sat_info.epoch,
             period: hiro_sat_info.period,
-            rarity: rarity,
+            rarity,
+            satributes: sat.satributes(),
         }))
     }
 }
@@ -39,7 +42,7 @@ impl IsService for ServiceHiroSatInfo {
 fn test_build_request() {
     let service = ServiceHiroSatInfo;
     let args = Args {
-        function: OrdFunction::SatInfo(SatInfoArgs{ ordinal: 85000000000 }),
+        function: OrdFunction::SatInfo(SatInfoArgs{ sat: crate::types::SatNotation::Integer(85000000000) }),
         max_kb_per_item: None,
     };
     assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/sats/85000000000");
@@ -50,7 +53,7 @@ fn test_build_request() {
 #[test]
 fn test_extract_response() {
 
-    use crate::types::SatoshiRarity;
+    use crate::types::{SatoshiRarity, Satribute};
 
     let bytes = r#"{
         "coinbase_height": 17,
@@ -72,6 +75,7 @@ fn test_extract_response() {
         epoch: 0,
         period: 0,
         rarity: SatoshiRarity::Uncommon,
+        satributes: vec![Satribute::Uncommon, Satribute::Vintage, Satribute::Alpha],
     }));
 }
 
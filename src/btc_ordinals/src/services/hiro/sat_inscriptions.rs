@@ -1,4 +1,4 @@
-use super::super::{IsService, Args, Response, OrdFunction, BASE_URLS};
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
 
 use crate::{types::{Provider, HiroSatInscriptions, OrdResult, SatInscriptionsArgs}, utils::deserialize_response};
 use std::ops::Add;
@@ -9,11 +9,10 @@ impl IsService for ServiceHiroSatInscriptions {
 
     fn get_url(&self, args: Args) -> String {
         let (ordinal, limit, offset) = match args.clone().function {
-            OrdFunction::SatInscriptions(SatInscriptionsArgs{ ordinal, limit, offset }) => (ordinal, limit, offset),
+            OrdFunction::SatInscriptions(SatInscriptionsArgs{ sat, limit, offset }) => (sat.parse().expect("Invalid sat notation"), limit, offset),
             _ => panic!("Invalid function: SatInscription expected"),
         };
-        BASE_URLS[&Provider::Hiro]
-            .clone()
+        base_url(Provider::Hiro)
             .add(
                 format!(
                     "/ordinals/v1/sats/{}/inscriptions?offset={}&limit={}",
@@ -36,7 +35,7 @@ fn test_build_request() {
 
     let service = ServiceHiroSatInscriptions;
     let args = Args {
-        function: OrdFunction::SatInscriptions(SatInscriptionsArgs{ ordinal: 947410401228752, offset: 0, limit: 2 }),
+        function: OrdFunction::SatInscriptions(SatInscriptionsArgs{ sat: crate::types::SatNotation::Name("hdgibjhzxoj".to_string()), offset: 0, limit: 2 }),
         max_kb_per_item: None,
     };
     assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/sats/947410401228752/inscriptions?offset=0&limit=2");
@@ -0,0 +1,140 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+use super::block_id_param;
+
+use crate::{http::QueryString, types::{Provider, HiroSatInscriptions, OrdResult, SearchInscriptionsArgs, InscriptionsOrderBy, SortOrder, SatoshiRarity}, utils::deserialize_response};
+use std::ops::Add;
+
+pub struct ServiceHiroSearchInscriptions;
+
+impl IsService for ServiceHiroSearchInscriptions {
+
+    fn get_url(&self, args: Args) -> String {
+        let args : SearchInscriptionsArgs = match args.function {
+            OrdFunction::SearchInscriptions(args) => args,
+            _ => panic!("Invalid function: SearchInscriptions expected"),
+        };
+        let rarities : Vec<_> = args.rarity.unwrap_or_default().iter().map(rarity_param).collect();
+        let query = QueryString::new()
+            .list_param("mime_type", &args.mime_type.unwrap_or_default())
+            .list_param("rarity", &rarities)
+            .opt_param("genesis_block", args.genesis_block.as_ref().map(block_id_param))
+            .opt_param("from_number", args.from_number)
+            .opt_param("to_number", args.to_number)
+            .opt_param("from_genesis_block_height", args.from_genesis_block_height)
+            .opt_param("to_genesis_block_height", args.to_genesis_block_height)
+            .opt_param("from_genesis_timestamp", args.from_genesis_timestamp)
+            .opt_param("to_genesis_timestamp", args.to_genesis_timestamp)
+            .opt_param("recursive", args.recursive)
+            .opt_param("cursed", args.cursed)
+            .opt_param("order_by", args.order_by.as_ref().map(order_by_param))
+            .opt_param("order", args.order.as_ref().map(order_param))
+            .param("offset", args.offset)
+            .param("limit", args.limit);
+        base_url(Provider::Hiro)
+            .add("/ordinals/v1/inscriptions")
+            .add(query.build().as_str())
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let inscriptions = deserialize_response::<HiroSatInscriptions>(bytes)?;
+        Ok(Response::SearchInscriptions(inscriptions))
+    }
+}
+
+fn rarity_param(rarity: &SatoshiRarity) -> &'static str {
+    match rarity {
+        SatoshiRarity::Common    => "common",
+        SatoshiRarity::Uncommon  => "uncommon",
+        SatoshiRarity::Rare      => "rare",
+        SatoshiRarity::Epic      => "epic",
+        SatoshiRarity::Legendary => "legendary",
+        SatoshiRarity::Mythic    => "mythic",
+    }
+}
+
+fn order_by_param(order_by: &InscriptionsOrderBy) -> &'static str {
+    match order_by {
+        InscriptionsOrderBy::Number             => "number",
+        InscriptionsOrderBy::GenesisBlockHeight => "genesis_block_height",
+        InscriptionsOrderBy::Ordinal            => "ordinal",
+        InscriptionsOrderBy::Rarity             => "rarity",
+    }
+}
+
+fn order_param(order: &SortOrder) -> &'static str {
+    match order {
+        SortOrder::Asc  => "asc",
+        SortOrder::Desc => "desc",
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    use crate::types::BlockId;
+
+    let service = ServiceHiroSearchInscriptions;
+
+    // Without any filter
+    let args = Args {
+        function: OrdFunction::SearchInscriptions(SearchInscriptionsArgs{ offset: 0, limit: 20, ..Default::default() }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://api.hiro.so/ordinals/v1/inscriptions?offset=0&limit=20");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+
+    // With every filter
+    let args = Args {
+        function: OrdFunction::SearchInscriptions(SearchInscriptionsArgs{
+            mime_type: Some(vec!["image/png".to_string(), "text/html".to_string()]),
+            rarity: Some(vec![SatoshiRarity::Uncommon, SatoshiRarity::Mythic]),
+            genesis_block: Some(BlockId::Height(775700)),
+            from_number: Some(-100),
+            to_number: Some(1000),
+            from_genesis_block_height: Some(775617),
+            to_genesis_block_height: Some(780000),
+            from_genesis_timestamp: Some(1675884508),
+            to_genesis_timestamp: Some(1677731361),
+            recursive: Some(false),
+            cursed: Some(true),
+            order_by: Some(InscriptionsOrderBy::GenesisBlockHeight),
+            order: Some(SortOrder::Desc),
+            offset: 40,
+            limit: 20,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args), "https://api.hiro.so/ordinals/v1/inscriptions?mime_type=image%2Fpng&mime_type=text%2Fhtml\
+        &rarity=uncommon&rarity=mythic&genesis_block=775700&from_number=-100&to_number=1000&from_genesis_block_height=775617&to_genesis_block_height=780000\
+        &from_genesis_timestamp=1675884508&to_genesis_timestamp=1677731361&recursive=false&cursed=true&order_by=genesis_block_height\
+        &order=desc&offset=40&limit=20");
+}
+
+#[test]
+fn test_extract_response() {
+    let bytes = r#"{
+        "limit": 20,
+        "offset": 0,
+        "total": 0,
+        "results": []
+      }"#.as_bytes();
+    let response = ServiceHiroSearchInscriptions.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::SearchInscriptions(HiroSatInscriptions {
+        limit: 20,
+        offset: 0,
+        total: 0,
+        results: vec![],
+    }));
+}
+
+#[test]
+fn test_validate_ranges() {
+
+    use crate::{types::OrdError, utils::validate_function};
+
+    let search = |from_number, to_number| OrdFunction::SearchInscriptions(SearchInscriptionsArgs{ from_number, to_number, offset: 0, limit: 20, ..Default::default() });
+    assert_eq!(validate_function(&search(Some(10), Some(10))), Ok(()));
+    assert_eq!(validate_function(&search(Some(10), None)), Ok(()));
+    assert_eq!(validate_function(&search(Some(10), Some(-10))), Err(OrdError::InvalidArgsError("Invalid number range: 10 > -10".to_string())));
+}
@@ -1,23 +1,41 @@
 use lazy_static::lazy_static;
 
-use ic_cdk::api::management_canister::http_request::HttpMethod;
+use ic_cdk::api::management_canister::http_request::{HttpHeader, HttpMethod};
 
 use crate::ONE_KIB;
-use crate::types::{Provider, EndPoint, Args, OrdFunction, Response, OrdError};
+use crate::config;
+use crate::types::{Provider, EndPoint, Args, OrdFunction, Response, OrdError, SatRangeArgs, SatRanges};
 
 use std::collections::BTreeMap;
 
 mod bitgem;
 mod hiro;
+mod ord;
 
 use bitgem::sat_range::ServiceBitgemSatRange;
 use bitgem::sat_info::ServiceBitgemSatInfo;
 use hiro::sat_info::ServiceHiroSatInfo;
 use hiro::sat_inscriptions::ServiceHiroSatInscriptions;
+use hiro::address_inscriptions::ServiceHiroAddressInscriptions;
+use hiro::search_inscriptions::ServiceHiroSearchInscriptions;
 use hiro::inscription_info::ServiceHiroInscriptionInfo;
+use hiro::inscription_transfers::ServiceHiroInscriptionTransfers;
 use hiro::inscription_content::ServiceHiroInscriptionContent;
+use hiro::block_transfers::ServiceHiroBlockTransfers;
+use hiro::block_inscription_activity::ServiceHiroBlockInscriptionActivity;
 use hiro::brc20_details::ServiceBrc20Details;
 use hiro::brc20_holders::ServiceBrc20Holders;
+use hiro::brc20_balances::ServiceBrc20Balances;
+use hiro::brc20_activity::ServiceBrc20Activity;
+use hiro::brc20_tokens::ServiceBrc20Tokens;
+use hiro::rune_details::ServiceRuneDetails;
+use hiro::rune_holders::ServiceRuneHolders;
+use hiro::rune_balances::ServiceRuneBalances;
+use hiro::rune_activity::ServiceRuneActivity;
+use ord::sat_info::ServiceOrdSatInfo;
+use ord::sat_range::ServiceOrdSatRange;
+use ord::inscription_info::ServiceOrdInscriptionInfo;
+use ord::inscription_content::ServiceOrdInscriptionContent;
 
 pub fn default_args(function: OrdFunction) -> Args {
     match function.clone() {
@@ -33,10 +51,22 @@ pub fn default_args(function: OrdFunction) -> Args {
             function,
             max_kb_per_item: Some(2), // 2 KiB should be enough for a single inscription, the size of the response body is approximatly 1400 bytes
         },
+        OrdFunction::AddressInscriptions(_) => Args {
+            function,
+            max_kb_per_item: Some(2), // 2 KiB (same as above)
+        },
+        OrdFunction::SearchInscriptions(_) => Args {
+            function,
+            max_kb_per_item: Some(2), // 2 KiB (same as above)
+        },
         OrdFunction::InscriptionInfo(_) => Args {
             function,
             max_kb_per_item: Some(2), // 2 kiB (same as above)
         },
+        OrdFunction::InscriptionTransfers(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be enough for a single transfer, the size of the response body is approximatly 400 bytes
+        },
         OrdFunction::InscriptionContent(_) => Args {
             function,
             max_kb_per_item: Some(5), // 5 KiB, set arbitrarily because the size of the inscription content can vary
@@ -49,16 +79,58 @@ pub fn default_args(function: OrdFunction) -> Args {
             function,
             max_kb_per_item: Some(1), // 1 Kib should be more than enough for a single brc20 holder, the size of the response body is approximatly 200 bytes
         },
+        OrdFunction::Brc20Balances(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be more than enough for a single brc20 balance, the size of the response body is approximatly 200 bytes
+        },
+        OrdFunction::Brc20Activity(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be enough for a single brc20 operation, the size of the response body is approximatly 700 bytes
+        },
+        OrdFunction::Brc20Tokens(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be enough for a single brc20 token, the size of the response body is approximatly 550 bytes
+        },
+        OrdFunction::RuneDetails(_) => Args {
+            function,
+            max_kb_per_item: Some(2), // 2 KiB should be enough for a single rune details, the size of the response body is approximatly 900 bytes
+        },
+        OrdFunction::RuneHolders(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be more than enough for a single rune holder, the size of the response body is approximatly 150 bytes
+        },
+        OrdFunction::RuneBalances(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be more than enough for a single rune balance, the size of the response body is approximatly 300 bytes
+        },
+        OrdFunction::RuneActivity(_) => Args {
+            function,
+            max_kb_per_item: Some(1), // 1 KiB should be enough for a single rune operation, the size of the response body is approximatly 700 bytes
+        },
+        OrdFunction::BlockInscriptionActivity(_) => Args {
+            function,
+            max_kb_per_item: Some(2), // 2 KiB should be enough for a single block transfer, the size of the response body is approximatly 900 bytes
+        },
     }
 }
 
 pub fn unwrap_max_response_bytes(args: Args) -> u64 {
     let num_items = match args.function.clone() {
         OrdFunction::SatInscriptions(args) => args.limit,
+        OrdFunction::AddressInscriptions(args) => args.limit,
+        OrdFunction::SearchInscriptions(args) => args.limit,
+        OrdFunction::InscriptionTransfers(args) => args.limit,
         OrdFunction::Brc20Holders(args) => args.limit,
+        OrdFunction::Brc20Balances(args) => args.limit,
+        OrdFunction::Brc20Activity(args) => args.limit,
+        OrdFunction::Brc20Tokens(args) => args.limit,
+        OrdFunction::RuneHolders(args) => args.limit,
+        OrdFunction::RuneBalances(args) => args.limit,
+        OrdFunction::RuneActivity(args) => args.limit,
+        OrdFunction::BlockInscriptionActivity(args) => args.limit,
         _ => 1,
     };
-    args.max_kb_per_item.expect("Max kbyte per item is missing") * num_items * ONE_KIB as u64
+    args.max_kb_per_item.expect("Max kbyte per item is missing") * num_items * ONE_KIB
 }
 
 pub trait IsService: Sync {
@@ -73,19 +145,69 @@ pub trait IsService: Sync {
         HttpMethod::GET
     }
 
+    // The headers required by the service, the configured headers of the provider come on top
+    fn get_headers(&self) -> Vec<HttpHeader> {
+        vec![]
+    }
+
     fn extract_response(&self, bytes: &[u8]) -> Result<Response, OrdError>;
+
+    // Completes the extracted response with what depends on the args, as the transform function
+    // has no access to them. It runs once the outcall returned, before the response gets cached.
+    fn complete_response(&self, _: &Args, response: Response) -> Result<Response, OrdError> {
+        Ok(response)
+    }
+
+    // Maps the response to the type shared by all the providers of the end point, so that the
+    // responses of the request method can be compared. The specific functions return the response as is.
+    fn common_response(&self, response: Response) -> Result<Response, OrdError> {
+        Ok(response)
+    }
+}
+
+// A composite service is served by several outcalls to the same provider, whose responses are then
+// merged together. Each part is the outcall of the service registered for its end point: the part
+// which is not worth an end point of its own is served by the service of the composite end point.
+pub trait IsCompositeService: Sync {
+
+    fn get_parts(&self, args: &Args) -> Vec<(EndPoint, Args)>;
+
+    fn merge_responses(&self, args: &Args, responses: Vec<Response>) -> Result<Response, OrdError>;
+}
+
+// Drop the common ranges of a sat range response if requested, only the exotic ranges are kept
+// This is done in-canister, so that every provider behaves the same whatever it supports
+pub fn exclude_common_ranges(args: &Args, response: Response) -> Response {
+    let exclude_common_ranges = match &args.function {
+        OrdFunction::SatRange(SatRangeArgs { exclude_common_ranges, .. }) => *exclude_common_ranges,
+        _ => panic!("Invalid function: SatRange expected"),
+    };
+    match response {
+        Response::SatRange(SatRanges { exotic_ranges, .. }) if exclude_common_ranges => Response::SatRange(SatRanges {
+            ranges: None,
+            exotic_ranges,
+        }),
+        response => response,
+    }
 }
 
+// The base URLs used unless overridden in the config
 // TODO: Use OnceCell instead
 lazy_static! {
-    pub static ref BASE_URLS: BTreeMap<Provider, String> = {
+    static ref DEFAULT_BASE_URLS: BTreeMap<Provider, String> = {
         let mut map = BTreeMap::new();
         map.insert(Provider::Bitgem, "https://api.bitgem.tech".to_string());
         map.insert(Provider::Hiro,   "https://api.hiro.so"    .to_string());
+        map.insert(Provider::Ord,    "https://ordinals.com"   .to_string());
         map
     };
 }
 
+// Return the base URL of the provider, the configured one if any
+pub fn base_url(provider: Provider) -> String {
+    config::base_url(provider).unwrap_or_else(|| DEFAULT_BASE_URLS[&provider].clone())
+}
+
 lazy_static! {
     pub static ref SERVICES: BTreeMap<(Provider, EndPoint), std::sync::Arc<dyn IsService + Send + Sync>> = {
         let mut map : BTreeMap<(Provider, EndPoint), std::sync::Arc<dyn IsService + Send + Sync>> = BTreeMap::new();
@@ -93,10 +215,34 @@ lazy_static! {
         map.insert((Provider::Bitgem, EndPoint::SatInfo           ), std::sync::Arc::new(ServiceBitgemSatInfo         ));
         map.insert((Provider::Hiro  , EndPoint::SatInfo           ), std::sync::Arc::new(ServiceHiroSatInfo           ));
         map.insert((Provider::Hiro  , EndPoint::SatInscriptions   ), std::sync::Arc::new(ServiceHiroSatInscriptions   ));
+        map.insert((Provider::Hiro  , EndPoint::AddressInscriptions), std::sync::Arc::new(ServiceHiroAddressInscriptions));
+        map.insert((Provider::Hiro  , EndPoint::SearchInscriptions), std::sync::Arc::new(ServiceHiroSearchInscriptions ));
         map.insert((Provider::Hiro  , EndPoint::InscriptionInfo   ), std::sync::Arc::new(ServiceHiroInscriptionInfo   ));
+        map.insert((Provider::Hiro  , EndPoint::InscriptionTransfers), std::sync::Arc::new(ServiceHiroInscriptionTransfers));
         map.insert((Provider::Hiro  , EndPoint::InscriptionContent), std::sync::Arc::new(ServiceHiroInscriptionContent));
         map.insert((Provider::Hiro  , EndPoint::Brc20Details      ), std::sync::Arc::new(ServiceBrc20Details          ));
         map.insert((Provider::Hiro  , EndPoint::Brc20Holders      ), std::sync::Arc::new(ServiceBrc20Holders          ));
+        map.insert((Provider::Hiro  , EndPoint::Brc20Balances     ), std::sync::Arc::new(ServiceBrc20Balances         ));
+        map.insert((Provider::Hiro  , EndPoint::Brc20Activity     ), std::sync::Arc::new(ServiceBrc20Activity         ));
+        map.insert((Provider::Hiro  , EndPoint::Brc20Tokens       ), std::sync::Arc::new(ServiceBrc20Tokens           ));
+        map.insert((Provider::Hiro  , EndPoint::RuneDetails       ), std::sync::Arc::new(ServiceRuneDetails           ));
+        map.insert((Provider::Hiro  , EndPoint::RuneHolders       ), std::sync::Arc::new(ServiceRuneHolders           ));
+        map.insert((Provider::Hiro  , EndPoint::RuneBalances      ), std::sync::Arc::new(ServiceRuneBalances          ));
+        map.insert((Provider::Hiro  , EndPoint::RuneActivity      ), std::sync::Arc::new(ServiceRuneActivity          ));
+        map.insert((Provider::Hiro  , EndPoint::BlockInscriptionActivity), std::sync::Arc::new(ServiceHiroBlockTransfers));
+        map.insert((Provider::Ord   , EndPoint::SatRange          ), std::sync::Arc::new(ServiceOrdSatRange           ));
+        map.insert((Provider::Ord   , EndPoint::SatInfo           ), std::sync::Arc::new(ServiceOrdSatInfo            ));
+        map.insert((Provider::Ord   , EndPoint::InscriptionInfo   ), std::sync::Arc::new(ServiceOrdInscriptionInfo    ));
+        map.insert((Provider::Ord   , EndPoint::InscriptionContent), std::sync::Arc::new(ServiceOrdInscriptionContent ));
+        map
+    };
+}
+
+// The composite services, their parts are served by the services above
+lazy_static! {
+    pub static ref COMPOSITE_SERVICES: BTreeMap<(Provider, EndPoint), std::sync::Arc<dyn IsCompositeService + Send + Sync>> = {
+        let mut map : BTreeMap<(Provider, EndPoint), std::sync::Arc<dyn IsCompositeService + Send + Sync>> = BTreeMap::new();
+        map.insert((Provider::Hiro  , EndPoint::BlockInscriptionActivity), std::sync::Arc::new(ServiceHiroBlockInscriptionActivity));
         map
     };
 }
@@ -104,16 +250,36 @@ lazy_static! {
 // Return the end point associated with the given ord function
 pub fn deduce_end_point(function: OrdFunction) -> EndPoint {
     match function {
-        OrdFunction::SatRange(_)           => EndPoint::SatRange,
-        OrdFunction::SatInfo(_)            => EndPoint::SatInfo,
-        OrdFunction::SatInscriptions(_)    => EndPoint::SatInscriptions,
-        OrdFunction::InscriptionInfo(_)    => EndPoint::InscriptionInfo,
-        OrdFunction::InscriptionContent(_) => EndPoint::InscriptionContent,
-        OrdFunction::Brc20Details(_)       => EndPoint::Brc20Details,
-        OrdFunction::Brc20Holders(_)       => EndPoint::Brc20Holders,
+        OrdFunction::SatRange(_)                 => EndPoint::SatRange,
+        OrdFunction::SatInfo(_)                  => EndPoint::SatInfo,
+        OrdFunction::SatInscriptions(_)          => EndPoint::SatInscriptions,
+        OrdFunction::AddressInscriptions(_)      => EndPoint::AddressInscriptions,
+        OrdFunction::SearchInscriptions(_)       => EndPoint::SearchInscriptions,
+        OrdFunction::InscriptionInfo(_)          => EndPoint::InscriptionInfo,
+        OrdFunction::InscriptionTransfers(_)     => EndPoint::InscriptionTransfers,
+        OrdFunction::InscriptionContent(_)       => EndPoint::InscriptionContent,
+        OrdFunction::Brc20Details(_)             => EndPoint::Brc20Details,
+        OrdFunction::Brc20Holders(_)             => EndPoint::Brc20Holders,
+        OrdFunction::Brc20Balances(_)            => EndPoint::Brc20Balances,
+        OrdFunction::Brc20Activity(_)            => EndPoint::Brc20Activity,
+        OrdFunction::Brc20Tokens(_)              => EndPoint::Brc20Tokens,
+        OrdFunction::RuneDetails(_)              => EndPoint::RuneDetails,
+        OrdFunction::RuneHolders(_)              => EndPoint::RuneHolders,
+        OrdFunction::RuneBalances(_)             => EndPoint::RuneBalances,
+        OrdFunction::RuneActivity(_)             => EndPoint::RuneActivity,
+        OrdFunction::BlockInscriptionActivity(_) => EndPoint::BlockInscriptionActivity,
     }
 }
 
+// Return the providers that serve the given end point, except the excluded ones
+pub fn fallback_providers(end_point: EndPoint, excluded: &[Provider]) -> Vec<Provider> {
+    SERVICES
+        .keys()
+        .filter(|key| key.1 == end_point && !excluded.contains(&key.0))
+        .map(|key| key.0)
+        .collect()
+}
+
 // Check that the given providers are available
 // If the required providers argument is empty, all available services are returned
 // Otherwise, if the required providers are all available, return them
@@ -122,7 +288,7 @@ pub fn validate_providers(required: Vec<Provider>, end_point: EndPoint) -> Resul
     let available: Vec<Provider> = SERVICES
         .iter()
         .filter(|(key, _)| key.1 == end_point)
-        .map(|(key, _)| key.0.clone())
+        .map(|(key, _)| key.0)
         .collect();
     if required.is_empty() {
         Ok(available)
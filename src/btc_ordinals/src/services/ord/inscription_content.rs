@@ -0,0 +1,60 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+
+use crate::types::{Provider, OrdResult, InscriptionContentArgs};
+use std::ops::Add;
+
+pub struct ServiceOrdInscriptionContent;
+
+// The content is served as is, hence no JSON header
+impl IsService for ServiceOrdInscriptionContent {
+
+    fn get_url(&self, args: Args) -> String {
+        let inscription_id = match args.function {
+            OrdFunction::InscriptionContent(InscriptionContentArgs{ inscription_id }) => inscription_id,
+            _ => panic!("Invalid function: InscriptionContent expected"),
+        };
+        base_url(Provider::Ord)
+            .add(
+                format!(
+                    "/content/{}",
+                    inscription_id,
+                )
+                .as_str(),
+            )
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        Ok(Response::InscriptionContent(bytes.to_vec()))
+    }
+}
+
+#[test]
+fn test_build_request() {
+    let service = ServiceOrdInscriptionContent;
+    let args = Args {
+        function: OrdFunction::InscriptionContent(InscriptionContentArgs{ inscription_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string() }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://ordinals.com/content/38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+    assert!(service.get_headers().is_empty());
+}
+
+#[test]
+fn test_extract_response() {
+    let bytes = r#"whatever"#.as_bytes();
+    let response = ServiceOrdInscriptionContent.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::InscriptionContent(bytes.to_vec()));
+}
+
+#[test]
+fn test_not_found() {
+
+    use crate::types::{EndPoint, OrdError};
+
+    // The 404 page of ord is not the content of the inscription
+    let context = candid::encode_args((Provider::Ord, EndPoint::InscriptionContent)).unwrap();
+    let bytes = r#"<!doctype html><html><head><title>Not Found</title></head><body><h1>404 Not Found</h1><p>inscription 38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci1 not found</p></body></html>"#.as_bytes();
+    assert_eq!(crate::extract_http_response(&context, 404, bytes), Err(OrdError::HttpStatusError{ status: 404 }));
+}
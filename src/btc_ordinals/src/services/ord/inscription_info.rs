@@ -0,0 +1,107 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+
+use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+use crate::{types::{Provider, OrdInscription, InscriptionInfo, OrdResult, OrdError, InscriptionInfoArgs}, utils::deserialize_response};
+use std::ops::Add;
+
+pub struct ServiceOrdInscriptionInfo;
+
+impl IsService for ServiceOrdInscriptionInfo {
+
+    fn get_url(&self, args: Args) -> String {
+        let inscription_id = match args.function {
+            OrdFunction::InscriptionInfo(InscriptionInfoArgs{ inscription_id }) => inscription_id,
+            _ => panic!("Invalid function: InscriptionInfo expected"),
+        };
+        base_url(Provider::Ord)
+            .add(
+                format!(
+                    "/inscription/{}",
+                    inscription_id,
+                )
+                .as_str(),
+            )
+    }
+
+    fn get_headers(&self) -> Vec<HttpHeader> {
+        super::json_headers()
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let inscription = deserialize_response::<OrdInscription>(bytes)?;
+        // The sat point is <txid>:<vout>:<offset>, the output is the part before the offset
+        let output = inscription.satpoint
+            .rsplit_once(':')
+            .map(|(output, _)| output.to_string())
+            .ok_or(OrdError::ResponseDecodingError(format!("Invalid satpoint: {}", inscription.satpoint)))?;
+        Ok(Response::InscriptionInfo(InscriptionInfo {
+            id: inscription.id,
+            number: inscription.number,
+            address: inscription.address,
+            genesis_height: inscription.height,
+            genesis_fee: inscription.fee,
+            genesis_timestamp: inscription.timestamp,
+            location: inscription.satpoint,
+            output,
+            value: inscription.value,
+            sat: inscription.sat,
+            content_type: inscription.content_type,
+            content_length: inscription.content_length,
+        }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+    let service = ServiceOrdInscriptionInfo;
+    let args = Args {
+        function: OrdFunction::InscriptionInfo(InscriptionInfoArgs{ inscription_id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string() }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://ordinals.com/inscription/38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+}
+
+#[test]
+fn test_extract_response() {
+
+    let bytes = r#"{
+        "address": "bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td",
+        "charms": [],
+        "child_count": 0,
+        "children": [],
+        "content_length": 208,
+        "content_type": "image/png",
+        "effective_content_type": "image/png",
+        "fee": 2805,
+        "height": 775617,
+        "id": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0",
+        "next": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci1",
+        "number": 19095,
+        "parents": [],
+        "previous": null,
+        "rune": null,
+        "sat": 257418248345364,
+        "satpoint": "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0",
+        "timestamp": 1675884508,
+        "value": 10000
+      }"#.as_bytes();
+
+    let response = ServiceOrdInscriptionInfo.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::InscriptionInfo(InscriptionInfo {
+        id: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dci0".to_string(),
+        number: 19095,
+        address: Some("bc1p3cyx5e2hgh53w7kpxcvm8s4kkega9gv5wfw7c4qxsvxl0u8x834qf0u2td".to_string()),
+        genesis_height: 775617,
+        genesis_fee: 2805,
+        genesis_timestamp: 1675884508,
+        location: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0:0".to_string(),
+        output: "38c46a8bf7ec90bc7f6b797e7dc84baa97f4e5fd4286b92fe1b50176d03b18dc:0".to_string(),
+        value: Some(10000),
+        sat: Some(257418248345364),
+        content_type: Some("image/png".to_string()),
+        content_length: Some(208),
+    }));
+}
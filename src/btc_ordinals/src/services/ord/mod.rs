@@ -0,0 +1,11 @@
+pub mod inscription_content;
+pub mod inscription_info;
+pub mod sat_info;
+pub mod sat_range;
+
+use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+// The ord server answers in HTML unless JSON is explicitly requested
+fn json_headers() -> Vec<HttpHeader> {
+    vec![HttpHeader{ name: "Accept".to_string(), value: "application/json".to_string() }]
+}
@@ -0,0 +1,92 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url};
+
+use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+use crate::{ordinals::Sat, types::{Provider, OrdSatInfo, SatInfo, OrdResult, OrdError, SatInfoArgs}, utils::{map_str_rarity, deserialize_response}};
+
+use std::ops::Add;
+
+pub struct ServiceOrdSatInfo;
+
+impl IsService for ServiceOrdSatInfo {
+
+    fn get_url(&self, args: Args) -> String {
+        let ordinal = match args.function {
+            OrdFunction::SatInfo(SatInfoArgs{ sat }) => sat.parse().expect("Invalid sat notation"),
+            _ => panic!("Invalid function: SatInfo expected"),
+        };
+        base_url(Provider::Ord)
+            .add(
+                format!("/sat/{}", ordinal)
+                    .as_str()
+            )
+    }
+
+    fn get_headers(&self) -> Vec<HttpHeader> {
+        super::json_headers()
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let ord_sat_info = deserialize_response::<OrdSatInfo>(bytes)?;
+        let rarity = map_str_rarity(&ord_sat_info.rarity)
+            .ok_or(OrdError::ResponseDecodingError(format!("Invalid rarity: {}", ord_sat_info.rarity)))?;
+        // The charms of ord do not match the satributes, these are deduced from the sat
+        let sat = Sat::new(ord_sat_info.number)
+            .map_err(|_| OrdError::ResponseDecodingError(format!("Invalid sat: {}", ord_sat_info.number)))?;
+        Ok(Response::SatInfo(SatInfo {
+            height: ord_sat_info.block,
+            cycle: ord_sat_info.cycle,
+            epoch: ord_sat_info.epoch,
+            period: ord_sat_info.period,
+            rarity,
+            satributes: sat.satributes(),
+        }))
+    }
+}
+
+#[test]
+fn test_build_request() {
+    let service = ServiceOrdSatInfo;
+    let args = Args {
+        function: OrdFunction::SatInfo(SatInfoArgs{ sat: crate::types::SatNotation::Integer(85000000000) }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://ordinals.com/sat/85000000000");
+    assert_eq!(service.get_body(args), None);
+    assert_eq!(service.get_method(), super::super::HttpMethod::GET);
+    assert_eq!(service.get_headers(), vec![HttpHeader{ name: "Accept".to_string(), value: "application/json".to_string() }]);
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::{SatoshiRarity, Satribute};
+
+    let bytes = r#"{
+        "block": 17,
+        "charms": ["uncommon", "vintage"],
+        "cycle": 0,
+        "decimal": "17.0",
+        "degree": "0°17′17″0‴",
+        "epoch": 0,
+        "inscriptions": [],
+        "name": "nvsstftmsmj",
+        "number": 85000000000,
+        "offset": 0,
+        "percentile": "0.004047619052071431%",
+        "period": 0,
+        "rarity": "uncommon",
+        "satpoint": null,
+        "timestamp": 1231475020
+      }"#.as_bytes();
+
+    let response = ServiceOrdSatInfo.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::SatInfo(SatInfo {
+        height: 17,
+        cycle: 0,
+        epoch: 0,
+        period: 0,
+        rarity: SatoshiRarity::Uncommon,
+        satributes: vec![Satribute::Uncommon, Satribute::Vintage, Satribute::Alpha],
+    }));
+}
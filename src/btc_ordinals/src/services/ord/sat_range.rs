@@ -0,0 +1,196 @@
+use super::super::{IsService, Args, Response, OrdFunction, base_url, exclude_common_ranges};
+
+use ic_cdk::api::management_canister::http_request::{HttpHeader, HttpMethod};
+
+use crate::{ordinals::exotic_ranges, types::{Provider, OrdOutput, OrdResult, OrdError, SatRange, SatRangeArgs, SatRanges}, utils::deserialize_response};
+
+pub struct ServiceOrdSatRange;
+
+// Uses the batch flavour of /output/<outpoint>, so that all the utxos are fetched with a single outcall
+impl IsService for ServiceOrdSatRange {
+
+    fn get_url(&self, _: Args) -> String {
+        base_url(Provider::Ord) + "/outputs"
+    }
+
+    fn get_body(&self, args: Args) -> Option<Vec<u8>> {
+        let utxos = match args.function {
+            OrdFunction::SatRange( SatRangeArgs { utxos, .. }) => utxos,
+            _ => panic!("Invalid function: SatRange expected"),
+        };
+
+        let utxo_strings: Vec<String> = utxos.iter().map(|utxo| format!(r#""{}:{}""#, utxo.txid, utxo.vout)).collect();
+        let body_json = format!("[{}]", utxo_strings.join(","));
+        Some(body_json.as_bytes().to_vec())
+    }
+
+    fn get_method(&self) -> HttpMethod {
+        HttpMethod::POST
+    }
+
+    fn get_headers(&self) -> Vec<HttpHeader> {
+        super::json_headers()
+    }
+
+    fn extract_response(&self, bytes: &[u8]) -> OrdResult {
+        let outputs = deserialize_response::<Vec<OrdOutput>>(bytes)?;
+        let mut ranges = vec![];
+        for output in outputs {
+            // The sat ranges are only given if the server indexes the sats
+            let sat_ranges = output.sat_ranges
+                .ok_or(OrdError::ResponseError(format!("No sat ranges for {}, the sats are not indexed", output.outpoint)))?;
+            let mut offset = 0;
+            for (start, end) in sat_ranges {
+                let size = end.checked_sub(start)
+                    .ok_or(OrdError::ResponseDecodingError(format!("Invalid sat range: {}-{}", start, end)))?;
+                ranges.push(SatRange {
+                    utxo: output.outpoint.clone(),
+                    start,
+                    size,
+                    end,
+                    offset,
+                });
+                offset += size;
+            }
+        }
+        // ord does not tell which sats are exotic, these are found in-canister
+        let exotic_ranges = exotic_ranges(&ranges)?;
+        Ok(Response::SatRange(SatRanges{
+            ranges: (!ranges.is_empty()).then_some(ranges),
+            exotic_ranges: (!exotic_ranges.is_empty()).then_some(exotic_ranges),
+        }))
+    }
+
+    fn complete_response(&self, args: &Args, response: Response) -> OrdResult {
+        Ok(exclude_common_ranges(args, response))
+    }
+}
+
+#[test]
+fn test_build_request() {
+
+    use crate::types::Utxo;
+
+    let service = ServiceOrdSatRange;
+    let args = Args {
+        function: OrdFunction::SatRange( SatRangeArgs{
+            utxos: vec![
+                Utxo {
+                    txid: "3de53b46b6a2bbf38587ac3cfc055eb2e960a8d25ff1361f2f15ef2bee9168aa".to_string(),
+                    vout : 0,
+                },
+                Utxo {
+                    txid: "3de53b46b6a2bbf38587ac3cfc055eb2e960a8d25ff1361f2f15ef2bee9168aa".to_string(),
+                    vout : 1,
+                }
+            ],
+            exclude_common_ranges: false,
+        }),
+        max_kb_per_item: None,
+    };
+    assert_eq!(service.get_url(args.clone()), "https://ordinals.com/outputs");
+    assert_eq!(
+        String::from_utf8(service.get_body(args).unwrap()).unwrap(),
+        r#"["3de53b46b6a2bbf38587ac3cfc055eb2e960a8d25ff1361f2f15ef2bee9168aa:0","3de53b46b6a2bbf38587ac3cfc055eb2e960a8d25ff1361f2f15ef2bee9168aa:1"]"#
+    );
+    assert_eq!(service.get_method(), HttpMethod::POST);
+}
+
+#[test]
+fn test_extract_response() {
+
+    use crate::types::{ExoticSatRange, SatoshiRarity, Satribute};
+
+    let service = ServiceOrdSatRange;
+    let bytes = r#"[
+        {
+            "address": "bc1qhuv3dhpnm0wktasd3v0kt6e4aqfqsd0uhfdu7d",
+            "indexed": true,
+            "inscriptions": [],
+            "outpoint": "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1",
+            "runes": {},
+            "sat_ranges": [[1846313750000000, 1846313750002121], [282004319175359, 282004319185359]],
+            "script_pubkey": "0014bf1916dc33dbdd65f60d8b1f65eb35e8120835fc",
+            "spent": false,
+            "transaction": "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b",
+            "value": 12121
+        }
+    ]"#.as_bytes();
+
+    let response = service.extract_response(bytes).unwrap();
+    assert_eq!(response, Response::SatRange(SatRanges {
+        ranges: Some(vec![
+            SatRange {
+                utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+                start: 1846313750000000,
+                size: 2121,
+                end: 1846313750002121,
+                offset: 0,
+            },
+            SatRange {
+                utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+                start: 282004319175359,
+                size: 10000,
+                end: 282004319185359,
+                offset: 2121,
+            },
+        ]),
+        exotic_ranges: Some(vec![
+            ExoticSatRange {
+                utxo: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1".to_string(),
+                start: 1846313750000000,
+                size: 1,
+                end: 1846313750000001,
+                offset: 0,
+                rarity: SatoshiRarity::Uncommon,
+                satributes: vec![Satribute::Uncommon],
+            },
+        ]),
+    }));
+}
+
+#[test]
+fn test_exclude_common_ranges() {
+
+    use crate::types::Utxo;
+
+    let bytes = r#"[
+        {
+            "outpoint": "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1",
+            "sat_ranges": [[1846313750000000, 1846313750002121]],
+            "value": 2121
+        }
+    ]"#.as_bytes();
+
+    let args = |exclude_common_ranges| Args {
+        function: OrdFunction::SatRange(SatRangeArgs{
+            utxos: vec![Utxo{ txid: "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b".to_string(), vout: 1 }],
+            exclude_common_ranges,
+        }),
+        max_kb_per_item: None,
+    };
+
+    let response = ServiceOrdSatRange.extract_response(bytes).unwrap();
+    assert_eq!(ServiceOrdSatRange.complete_response(&args(false), response.clone()).unwrap(), response);
+    match ServiceOrdSatRange.complete_response(&args(true), response).unwrap() {
+        Response::SatRange(SatRanges{ ranges, exotic_ranges }) => {
+            assert_eq!(ranges, None);
+            assert_eq!(exotic_ranges.map(|exotic_ranges| exotic_ranges.len()), Some(1));
+        },
+        _ => panic!("Unexpected response type"),
+    }
+}
+
+#[test]
+fn test_sats_not_indexed() {
+
+    let bytes = r#"[
+        {
+            "outpoint": "1741de211d2905565bd5c07089cbae5719448fa597966e6612f76420416a0f8b:1",
+            "sat_ranges": null,
+            "value": 12121
+        }
+    ]"#.as_bytes();
+
+    assert!(matches!(ServiceOrdSatRange.extract_response(bytes), Err(OrdError::ResponseError(_))));
+}
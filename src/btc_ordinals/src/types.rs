@@ -1,6 +1,7 @@
-use candid::{CandidType, Deserialize};
+use candid::{CandidType, Deserialize, Nat};
 
 use ic_cdk::api::call::RejectionCode;
+use ic_cdk::api::management_canister::http_request::HttpHeader;
 
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
 pub struct HttpSendError {
@@ -23,7 +24,28 @@ pub enum OrdError {
         expected: u128,
         received: u128,
     },
-    UnexpectedResponseTypeError(Response),
+    // Boxed, so that the results do not get as large as the biggest response
+    UnexpectedResponseTypeError(Box<Response>),
+    HttpStatusError {
+        status: u16,
+    },
+    EndPointMismatchError {
+        expected: EndPoint,
+        actual: EndPoint,
+    },
+    InvalidConfigError(String),
+    InvalidArgsError(String),
+    SatOutOfRangeError {
+        ordinal: u64,
+    },
+    InvalidSatNotationError(SatNotation),
+    SatRangeTooLargeError {
+        max_checked_sats: u64,
+    },
+    InvalidAmountError {
+        field: String,
+        amount: String,
+    },
 }
 
 pub type OrdResult = Result<Response, OrdError>;
@@ -38,12 +60,38 @@ pub struct ProviderOrdResult {
 pub enum MultiOrdResult {
     Consistent(OrdResult),
     Inconsistent(Vec<ProviderOrdResult>),
+    Consensus {
+        result: Response,
+        agreed: Vec<Provider>,
+        dissented: Vec<ProviderOrdResult>,
+        failed: Vec<ProviderOrdError>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize)]
+pub struct ProviderOrdError {
+    pub provider: Provider,
+    pub error: OrdError,
+}
+
+// Defines how the results of the different providers are reduced into a single one
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize)]
+pub enum ConsensusStrategy {
+    // All the providers shall return the same result
+    Equality,
+    // At least the given number of providers shall return the same successful response
+    Threshold { min: u8 },
+    // More than half of the providers shall return the same successful response
+    Majority,
+    // The first successful response (in the providers order) is taken
+    FirstSuccess,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize, Copy, Clone, Hash)]
 pub enum Provider {
     Hiro,
     Bitgem,
+    Ord,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -51,6 +99,8 @@ pub struct OrdArgs {
     pub function: OrdFunction,
     pub providers: Vec<Provider>,
     pub max_kb_per_item: Option<u64>,
+    pub consensus: Option<ConsensusStrategy>,
+    pub ignore_errors: Option<bool>,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -64,10 +114,21 @@ pub enum OrdFunction {
     SatRange(SatRangeArgs),
     SatInfo(SatInfoArgs),
     SatInscriptions(SatInscriptionsArgs),
+    AddressInscriptions(AddressInscriptionsArgs),
+    SearchInscriptions(SearchInscriptionsArgs),
+    InscriptionTransfers(InscriptionTransfersArgs),
+    BlockInscriptionActivity(BlockActivityArgs),
     InscriptionInfo(InscriptionInfoArgs),
     InscriptionContent(InscriptionContentArgs),
     Brc20Details(Brc20DetailsArgs),
     Brc20Holders(Brc20HoldersArgs),
+    Brc20Balances(Brc20BalancesArgs),
+    Brc20Activity(Brc20ActivityArgs),
+    Brc20Tokens(Brc20TokensArgs),
+    RuneDetails(RuneDetailsArgs),
+    RuneHolders(RuneHoldersArgs),
+    RuneBalances(RuneBalancesArgs),
+    RuneActivity(RuneActivityArgs),
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -76,23 +137,95 @@ pub struct SatRangeArgs {
     pub exclude_common_ranges: bool,
 }
 
+// The different notations to refer to a sat, see https://docs.ordinals.com/overview.html
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum SatNotation {
+    Integer(u64),    // e.g. 2099994106992659
+    Name(String),    // e.g. "satoshi"
+    Degree(String),  // e.g. "3°111094′214″16797‴"
+    Decimal(String), // e.g. "3891094.16797"
+    Percentile(String), // e.g. "99.99971949060254%"
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize)]
 pub struct SatInfoArgs {
-    pub ordinal: u64
+    pub sat: SatNotation,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
 pub struct SatInscriptionsArgs {
-    pub ordinal: u64,
+    pub sat: SatNotation,
     pub offset: u64,
     pub limit: u64,
 }
 
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct AddressInscriptionsArgs {
+    pub address: String,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+// The filters are all optional, the lists match any of their values
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct SearchInscriptionsArgs {
+    pub mime_type: Option<Vec<String>>,
+    pub rarity: Option<Vec<SatoshiRarity>>,
+    pub genesis_block: Option<BlockId>,
+    pub from_number: Option<i64>,
+    pub to_number: Option<i64>,
+    pub from_genesis_block_height: Option<u64>,
+    pub to_genesis_block_height: Option<u64>,
+    pub from_genesis_timestamp: Option<u64>, // In seconds
+    pub to_genesis_timestamp: Option<u64>,   // In seconds
+    pub recursive: Option<bool>,
+    pub cursed: Option<bool>,
+    pub order_by: Option<InscriptionsOrderBy>,
+    pub order: Option<SortOrder>,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum InscriptionsOrderBy {
+    Number,
+    GenesisBlockHeight,
+    Ordinal,
+    Rarity,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize)]
 pub struct InscriptionInfoArgs {
     pub inscription_id: String
 }
 
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct InscriptionTransfersArgs {
+    pub inscription_id: String,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+// A block is referred to by its height or its hash
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum BlockId {
+    Height(u64),
+    Hash(String),
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct BlockActivityArgs {
+    pub block: BlockId,
+    pub offset: u64,
+    pub limit: u64,
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize)]
 pub struct InscriptionContentArgs {
     pub inscription_id: String
@@ -110,15 +243,103 @@ pub struct Brc20HoldersArgs {
     pub limit: u64,
 }
 
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Brc20BalancesArgs {
+    pub address: String,
+    pub ticker: Option<String>,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+// Every filter is optional, the lists match any of their values
+#[derive(Clone, Debug, CandidType, Deserialize, Default)]
+pub struct Brc20ActivityArgs {
+    pub ticker: Option<Vec<String>>,
+    pub operation: Option<Vec<Brc20OperationKind>>,
+    pub block_height: Option<u64>,
+    pub address: Option<String>,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Brc20OperationKind {
+    Deploy,
+    Mint,
+    Transfer,
+    TransferSend,
+    TransferReceive,
+}
+
+// The tokens whose ticker starts with any of the given prefixes
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Brc20TokensArgs {
+    pub ticker_prefix: Option<Vec<String>>,
+    pub order_by: Option<Brc20TokensOrderBy>,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum Brc20TokensOrderBy {
+    DeployTimestamp,
+    TxCount,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum RuneId {
+    Name(String),               // e.g. UNCOMMON•GOODS, the spacers are optional
+    Id { block: u64, tx: u32 }, // The etching transaction, e.g. 840000:1
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RuneDetailsArgs {
+    pub rune: RuneId,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RuneHoldersArgs {
+    pub rune: RuneId,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RuneBalancesArgs {
+    pub address: String,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+// The activity of a rune, of an address, or of an address on a rune
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RuneActivityArgs {
+    pub rune: Option<RuneId>,
+    pub address: Option<String>,
+    pub offset: u64,
+    pub limit: u64,
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize, Copy, Clone)]
 pub enum EndPoint {
     SatRange,
     SatInfo,
     SatInscriptions,
+    AddressInscriptions,
+    SearchInscriptions,
     InscriptionInfo,
+    InscriptionTransfers,
+    BlockInscriptionActivity,
     InscriptionContent,
     Brc20Details,
     Brc20Holders,
+    Brc20Balances,
+    Brc20Activity,
+    Brc20Tokens,
+    RuneDetails,
+    RuneHolders,
+    RuneBalances,
+    RuneActivity,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
@@ -126,10 +347,23 @@ pub enum Response {
     SatRange(SatRanges),
     SatInfo(SatInfo),
     SatInscriptions(HiroSatInscriptions),
-    InscriptionInfo(HiroSatInscription),
+    AddressInscriptions(HiroSatInscriptions),
+    SearchInscriptions(HiroSatInscriptions),
+    InscriptionInfo(InscriptionInfo),
+    // The full Hiro inscription, only returned by hiro_inscription_info
+    HiroInscriptionInfo(HiroSatInscription),
+    InscriptionTransfers(InscriptionTransfers),
+    BlockInscriptionActivity(BlockInscriptionActivity),
     InscriptionContent(InscriptionContent),
     Brc20Details(HiroBrc20Details),
-    Brc20Holders(HiroBrc20Holders)
+    Brc20Holders(HiroBrc20Holders),
+    Brc20Balances(HiroBrc20Balances),
+    Brc20Activity(Brc20Activity),
+    Brc20Tokens(HiroBrc20Tokens),
+    RuneDetails(HiroRuneDetails),
+    RuneHolders(HiroRuneHolders),
+    RuneBalances(HiroRuneBalances),
+    RuneActivity(HiroRuneActivity),
 }
 
 
@@ -169,7 +403,7 @@ pub struct ExoticSatRange {
     pub end : u64,
     pub offset : u64,
     pub rarity : SatoshiRarity,
-    pub satributes : Vec<String>,
+    pub satributes : Vec<Satribute>,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
@@ -208,6 +442,43 @@ pub struct BitgemSatInfo {
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct OrdSatInfo {
+    pub number: u64,
+    pub decimal: String,
+    pub degree: String,
+    pub name: String,
+    pub block: u64,
+    pub cycle: u64,
+    pub epoch: u64,
+    pub period: u64,
+    pub offset: u64,
+    pub rarity: String,
+    pub percentile: String,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct OrdInscription {
+    pub id: String,
+    pub number: i64,
+    pub address: Option<String>,
+    pub content_length: Option<u64>,
+    pub content_type: Option<String>,
+    pub fee: u64,
+    pub height: u64,
+    pub sat: Option<u64>,
+    pub satpoint: String,
+    pub timestamp: u64,
+    pub value: Option<u64>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct OrdOutput {
+    pub outpoint: String,
+    pub sat_ranges: Option<Vec<(u64, u64)>>,
+    pub value: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
 pub enum SatoshiRarity {
     Common,
     Uncommon,
@@ -217,6 +488,30 @@ pub enum SatoshiRarity {
     Mythic,
 }
 
+// The notable attributes of a sat: its rarity (unless common) and the exotic categories it
+// belongs to. The lists of satributes are kept sorted in this order, so they can be compared.
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Satribute {
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+    Mythic,
+    Black,            // Last sat of a block
+    Vintage,          // Mined in the first 1000 blocks
+    Alpha,            // First sat of a bitcoin
+    Omega,            // Last sat of a bitcoin
+    Palindrome,       // The ordinal number reads the same in both directions
+    Block9,           // Mined in block 9, the oldest sats in circulation
+    Block78,          // Mined in block 78, by Hal Finney
+    Nakamoto,         // Mined by Satoshi Nakamoto
+    FirstTransaction, // Sent in the first bitcoin transaction, from Satoshi Nakamoto to Hal Finney
+    Pizza,            // Spent in the bitcoin pizza transaction
+    Hitman,           // Spent in the Ross Ulbricht hitman transaction
+    Jpeg,             // Spent in the first purchase of a JPEG with bitcoin
+    Other(String),    // Any satribute unknown to the canister, as returned by the provider
+}
+
 // Common ordinal info struct, contains the fiels that are common to all providers
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
 pub struct SatInfo {
@@ -225,12 +520,23 @@ pub struct SatInfo {
     pub epoch: u64,
     pub period: u64,
     pub rarity: SatoshiRarity,
+    pub satributes: Vec<Satribute>,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
-pub struct HiroInscriptionContentArgs {
-    pub inscription_id: String,
-    pub max_content_kb: u64,
+// Sat info computed in-canister, contains the same fields as the Hiro sat info
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct LocalSatInfo {
+    pub height: u64,
+    pub cycle: u64,
+    pub epoch: u64,
+    pub period: u64,
+    pub offset: u64,
+    pub rarity: SatoshiRarity,
+    pub satributes: Vec<Satribute>,
+    pub degree: String,
+    pub decimal: String,
+    pub name: String,
+    pub percentile: String,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
@@ -261,6 +567,108 @@ pub struct HiroSatInscription {
     pub recursion_refs: Option<Vec<String>>,
 }
 
+// Common inscription info struct, contains the fields that are common to all providers
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct InscriptionInfo {
+    pub id: String,
+    pub number: i64,
+    pub address: Option<String>,
+    pub genesis_height: u64,
+    pub genesis_fee: u64,
+    pub genesis_timestamp: u64, // In seconds
+    pub location: String,       // The sat point, i.e. <txid>:<vout>:<offset>
+    pub output: String,         // <txid>:<vout>
+    pub value: Option<u64>,
+    pub sat: Option<u64>,       // Only known if the provider indexes the sats
+    pub content_type: Option<String>,
+    pub content_length: Option<u64>,
+}
+
+// The locations of an inscription, from the most recent one
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct InscriptionTransfers {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<InscriptionTransfer>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct InscriptionTransfer {
+    pub block_height: u64,
+    pub block_hash: String,
+    pub address: Option<String>, // None if the inscription was burnt
+    pub tx_id: String,
+    pub location: String,        // The sat point, i.e. <txid>:<vout>:<offset>
+    pub output: String,          // <txid>:<vout>
+    pub value: u64,
+    pub offset: u64,
+    pub timestamp: u64,          // In seconds
+}
+
+// An inscription transferred in a block, with its previous and new locations
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct BlockTransfer {
+    pub id: String,
+    pub number: i64,
+    pub from: InscriptionTransfer,
+    pub to: InscriptionTransfer,
+}
+
+// The inscriptions created and transferred in a block. The offset and limit apply to each kind
+// of activity, hence a page holds up to limit inscriptions and limit transfers.
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct BlockInscriptionActivity {
+    pub limit: u64,
+    pub offset: u64,
+    pub total_inscriptions: u64,
+    pub total_transfers: u64,
+    pub results: Vec<InscriptionActivity>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum InscriptionActivity {
+    Inscribed(InscriptionInfo),
+    Transferred(BlockTransfer),
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBlockTransfers {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBlockTransfer>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBlockTransfer {
+    pub id: String,
+    pub number: i64,
+    pub from: HiroInscriptionTransfer,
+    pub to: HiroInscriptionTransfer,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroInscriptionTransfers {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroInscriptionTransfer>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroInscriptionTransfer {
+    pub block_height: u64,
+    pub block_hash: String,
+    pub address: Option<String>,
+    pub tx_id: String,
+    pub location: String,
+    pub output: String,
+    pub value: String,
+    pub offset: String,
+    pub timestamp: u64,
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
 pub struct HiroSatInscriptions {
     pub limit: u64,
@@ -271,6 +679,14 @@ pub struct HiroSatInscriptions {
 
 pub type InscriptionContent = Vec<u8>;
 
+// A BRC-20 amount as an integer scaled by 10^18, the maximum number of decimals of a token,
+// e.g. "1000.5" is 1000500000000000000000 whatever the decimals of the token.
+// The supplies and limits are checked against the decimals of their token. The balances, the
+// holders and the mint and transfer amounts of the activity come without them, hence these are
+// only checked against 18 decimals: an amount with more decimals than its token allows is accepted.
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Brc20Amount(pub Nat);
+
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
 pub struct HiroBrc20Details {
     pub token: HiroBrc20Token,
@@ -285,18 +701,26 @@ pub struct HiroBrc20Token {
     pub tx_id: String,
     pub address: String,
     pub ticker: String,
-    pub max_supply: String,
-    pub mint_limit: String,
+    pub max_supply: Brc20Amount,
+    pub mint_limit: Brc20Amount,
     pub decimals: u64,
     pub deploy_timestamp: u64,
-    pub minted_supply: String,
+    pub minted_supply: Brc20Amount,
     pub tx_count: u64,
 }
 
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Tokens {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBrc20Token>,
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
 pub struct HiroBrc20Supply {
-    pub max_supply: String,
-    pub minted_supply: String,
+    pub max_supply: Brc20Amount,
+    pub minted_supply: Brc20Amount,
     pub holders: u64,
 }
 
@@ -310,15 +734,363 @@ pub struct HiroBrc20Holders {
 
 #[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
 pub struct HiroBrc20Holder {
+    pub address: String,
+    pub overall_balance: Brc20Amount,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Balances {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBrc20Balance>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Balance {
+    pub ticker: String,
+    pub available_balance: Brc20Amount,
+    pub transferrable_balance: Brc20Amount,
+    pub overall_balance: Brc20Amount,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct Brc20Activity {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<Brc20ActivityEntry>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct Brc20ActivityEntry {
+    pub ticker: String,
+    pub inscription_id: String,
+    pub location: String,
+    pub block_height: u64,
+    pub block_hash: String,
+    pub tx_id: String,
+    pub timestamp: u64, // In seconds
+    pub operation: Brc20Operation,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub enum Brc20Operation {
+    Deploy {
+        address: String,
+        max_supply: Brc20Amount,
+        mint_limit: Brc20Amount,
+        decimals: u8,
+    },
+    Mint {
+        address: String,
+        amount: Brc20Amount,
+    },
+    // The inscription of a transfer, the amount becomes transferrable for the address
+    Transfer {
+        address: String,
+        amount: Brc20Amount,
+    },
+    // The two sides of a transfer, listed apart so that the activity of an address shows both
+    TransferSend {
+        from_address: String,
+        to_address: String,
+        amount: Brc20Amount,
+    },
+    TransferReceive {
+        from_address: String,
+        to_address: String,
+        amount: Brc20Amount,
+    },
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Activity {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBrc20ActivityEntry>,
+}
+
+// Only the details of the given operation are set
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20ActivityEntry {
+    pub operation: String,
+    pub ticker: String,
+    pub inscription_id: String,
+    pub block_height: u64,
+    pub block_hash: String,
+    pub tx_id: String,
+    pub location: String,
+    pub address: String,
+    pub timestamp: u64, // In milliseconds
+    pub deploy: Option<HiroBrc20Deploy>,
+    pub mint: Option<HiroBrc20Mint>,
+    pub transfer: Option<HiroBrc20Transfer>,
+    pub transfer_send: Option<HiroBrc20TransferSend>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Deploy {
+    pub max_supply: String,
+    pub mint_limit: String,
+    pub decimals: u8,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Mint {
+    pub amount: String,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20Transfer {
+    pub amount: String,
+    pub from_address: String,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroBrc20TransferSend {
+    pub amount: String,
+    pub from_address: String,
+    pub to_address: String,
+}
+
+// The BRC-20 responses as given by Hiro, with the amounts as decimal strings
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20DetailsJson {
+    pub token: HiroBrc20TokenJson,
+    pub supply: HiroBrc20SupplyJson,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20TokenJson {
+    pub id: String,
+    pub number: u64,
+    pub block_height: u64,
+    pub tx_id: String,
+    pub address: String,
+    pub ticker: String,
+    pub max_supply: String,
+    pub mint_limit: String,
+    pub decimals: u64,
+    pub deploy_timestamp: u64,
+    pub minted_supply: String,
+    pub tx_count: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20SupplyJson {
+    pub max_supply: String,
+    pub minted_supply: String,
+    pub holders: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20TokensJson {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBrc20TokenJson>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20HoldersJson {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBrc20HolderJson>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20HolderJson {
     pub address: String,
     pub overall_balance: String,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
-pub struct HiroBrc20HoldersArgs {
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20BalancesJson {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroBrc20BalanceJson>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroBrc20BalanceJson {
     pub ticker: String,
+    pub available_balance: String,
+    pub transferrable_balance: String,
+    pub overall_balance: String,
+}
+
+// A rune amount as an integer in the smallest unit of the rune, i.e. scaled by 10^divisibility,
+// e.g. "1000.00000" of a rune with a divisibility of 5 is 100000000
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
+pub struct RuneAmount(pub Nat);
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneDetails {
+    pub id: String,
+    pub name: String,
+    pub spaced_name: String,
+    pub number: u64,
+    pub divisibility: u8,
+    pub symbol: String,
+    pub turbo: bool,
+    pub mint_terms: HiroRuneMintTerms,
+    pub supply: HiroRuneSupply,
+    pub location: HiroRuneLocation,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneMintTerms {
+    pub amount: Option<RuneAmount>,
+    pub cap: Option<Nat>, // The number of mints
+    pub height_start: Option<u64>,
+    pub height_end: Option<u64>,
+    pub offset_start: Option<u64>,
+    pub offset_end: Option<u64>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneSupply {
+    pub current: RuneAmount,
+    pub minted: RuneAmount,
+    pub total_mints: Nat,
+    pub mint_percentage: String, // For display only, e.g. "12.3456"
+    pub mintable: bool,
+    pub burned: RuneAmount,
+    pub total_burns: Nat,
+    pub premine: RuneAmount,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneLocation {
+    pub block_hash: String,
+    pub block_height: u64,
+    pub tx_id: String,
+    pub tx_index: u32,
+    pub vout: Option<u32>,
+    pub output: Option<String>,
+    pub timestamp: u64, // In seconds
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRune {
+    pub id: String,
+    pub number: u64,
+    pub name: String,
+    pub spaced_name: String,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneHolders {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroRuneHolder>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneHolder {
+    pub address: Option<String>,
+    pub balance: String, // The decimal string given by Hiro, the divisibility is not part of the response
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneBalances {
+    pub limit: u64,
+    pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroRuneBalance>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneBalance {
+    pub rune: HiroRune,
+    pub address: Option<String>,
+    pub balance: String, // Same as above
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneActivity {
     pub limit: u64,
     pub offset: u64,
+    pub total: u64,
+    pub results: Vec<HiroRuneActivityEntry>,
+}
+
+// The operation is one of etching, mint, burn, send and receive
+#[derive(Clone, Debug, CandidType, Deserialize, Eq, PartialEq)]
+pub struct HiroRuneActivityEntry {
+    pub rune: HiroRune,
+    pub operation: String,
+    pub address: Option<String>,
+    pub receiver_address: Option<String>,
+    pub amount: Option<String>, // Same as above
+    pub location: HiroRuneLocation,
+}
+
+// The rune details as given by Hiro, with the amounts as decimal strings
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroRuneDetailsJson {
+    pub id: String,
+    pub name: String,
+    pub spaced_name: String,
+    pub number: u64,
+    pub divisibility: u8,
+    pub symbol: String,
+    pub turbo: bool,
+    pub mint_terms: HiroRuneMintTermsJson,
+    pub supply: HiroRuneSupplyJson,
+    pub location: HiroRuneLocation,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroRuneMintTermsJson {
+    pub amount: Option<String>,
+    pub cap: Option<String>,
+    pub height_start: Option<u64>,
+    pub height_end: Option<u64>,
+    pub offset_start: Option<u64>,
+    pub offset_end: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HiroRuneSupplyJson {
+    pub current: String,
+    pub minted: String,
+    pub total_mints: String,
+    pub mint_percentage: String,
+    pub mintable: bool,
+    pub burned: String,
+    pub total_burns: String,
+    pub premine: String,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Default)]
+pub struct InitArgs {
+    pub subnet_size: Option<u32>,
+    pub providers: Option<Vec<ProviderConfig>>,
+}
+
+/// The settings of a provider, the ones left empty keep their current value.
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct ProviderConfig {
+    pub provider: Provider,
+    pub base_url: Option<String>,
+    // Replaces all the headers of the provider, an empty list removes them
+    pub headers: Option<Vec<HttpHeader>>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub bytes_used: u64,
+    pub evictions: u64,
 }
 
 #[derive(Deserialize)]
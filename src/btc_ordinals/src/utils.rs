@@ -1,4 +1,10 @@
-use crate::types::{ SatoshiRarity, JsonError, OrdError, OrdArgs, Args };
+use crate::types::{ SatoshiRarity, Satribute, JsonError, OrdError, OrdArgs, Args, OrdFunction, Utxo, InscriptionInfoArgs,
+    InscriptionContentArgs, Brc20DetailsArgs, Brc20HoldersArgs, SatRangeArgs, OrdResult, HttpSendError, SatInfoArgs,
+    SatInscriptionsArgs, AddressInscriptionsArgs, SearchInscriptionsArgs, InscriptionTransfersArgs, SatNotation, BlockActivityArgs, BlockId,
+    Brc20BalancesArgs, Brc20ActivityArgs, Brc20TokensArgs, RuneId, RuneDetailsArgs, RuneHoldersArgs, RuneBalancesArgs,
+    RuneActivityArgs };
+
+use ic_cdk::api::call::RejectionCode;
 
 pub fn map_str_rarity(rarity: &str) -> Option<SatoshiRarity> {
     match rarity.to_lowercase().as_str() {
@@ -13,6 +19,86 @@ pub fn map_str_rarity(rarity: &str) -> Option<SatoshiRarity> {
 }
 
 
+pub fn map_rarity_satribute(rarity: &SatoshiRarity) -> Option<Satribute> {
+    match rarity {
+        SatoshiRarity::Common    => None,
+        SatoshiRarity::Uncommon  => Some(Satribute::Uncommon),
+        SatoshiRarity::Rare      => Some(Satribute::Rare),
+        SatoshiRarity::Epic      => Some(Satribute::Epic),
+        SatoshiRarity::Legendary => Some(Satribute::Legendary),
+        SatoshiRarity::Mythic    => Some(Satribute::Mythic),
+    }
+}
+
+pub fn map_satribute_rarity(satribute: &Satribute) -> Option<SatoshiRarity> {
+    match satribute {
+        Satribute::Uncommon  => Some(SatoshiRarity::Uncommon),
+        Satribute::Rare      => Some(SatoshiRarity::Rare),
+        Satribute::Epic      => Some(SatoshiRarity::Epic),
+        Satribute::Legendary => Some(SatoshiRarity::Legendary),
+        Satribute::Mythic    => Some(SatoshiRarity::Mythic),
+        _                    => None,
+    }
+}
+
+// The separators and the case are ignored (e.g. "block_9", "Block 9" and "block9" are the same),
+// the unknown satributes are kept as is
+pub fn map_str_satribute(satribute: &str) -> Satribute {
+    let key : String = satribute
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    match key.as_str() {
+        "uncommon"         => Satribute::Uncommon,
+        "rare"             => Satribute::Rare,
+        "epic"             => Satribute::Epic,
+        "legendary"        => Satribute::Legendary,
+        "mythic"           => Satribute::Mythic,
+        "black"            => Satribute::Black,
+        "vintage"          => Satribute::Vintage,
+        "alpha"            => Satribute::Alpha,
+        "omega"            => Satribute::Omega,
+        "palindrome"       => Satribute::Palindrome,
+        "block9"           => Satribute::Block9,
+        "block78"          => Satribute::Block78,
+        "nakamoto"         => Satribute::Nakamoto,
+        "firsttransaction" => Satribute::FirstTransaction,
+        "pizza"            => Satribute::Pizza,
+        "hitman"           => Satribute::Hitman,
+        "jpeg"             => Satribute::Jpeg,
+        _                  => Satribute::Other(satribute.to_string()),
+    }
+}
+
+// Map the satributes returned by a provider, sorted and without duplicates. The rarity is
+// the highest one found, common if none.
+pub fn map_str_satributes(satributes: &[String]) -> (SatoshiRarity, Vec<Satribute>) {
+    let mut satributes : Vec<Satribute> = satributes.iter().map(|satribute| map_str_satribute(satribute)).collect();
+    satributes.sort();
+    satributes.dedup();
+    let rarity = satributes
+        .iter()
+        .filter_map(map_satribute_rarity)
+        .max()
+        .unwrap_or(SatoshiRarity::Common);
+    (rarity, satributes)
+}
+
+// Only keep the satributes which derive from the ordinal number. The historical ones (e.g. pizza)
+// and the unknown ones are not returned by every provider, they would make the consensus fail.
+pub fn arithmetic_satributes(satributes: Vec<Satribute>) -> Vec<Satribute> {
+    satributes.into_iter().filter(is_arithmetic_satribute).collect()
+}
+
+fn is_arithmetic_satribute(satribute: &Satribute) -> bool {
+    matches!(satribute,
+        Satribute::Uncommon | Satribute::Rare | Satribute::Epic | Satribute::Legendary | Satribute::Mythic |
+        Satribute::Black | Satribute::Vintage | Satribute::Alpha | Satribute::Omega | Satribute::Palindrome |
+        Satribute::Block9 | Satribute::Block78
+    )
+}
+
 pub fn deserialize_response<'a, T>(
     bytes: &'a [u8]
 ) 
@@ -43,3 +129,246 @@ pub fn from_ord_args(ord_args: OrdArgs) -> Args {
         max_kb_per_item: ord_args.max_kb_per_item,
     }
 }
+
+// Check that the sat notation of the function, if any, refers to an existing sat,
+// and that the address, if any, is fit to be part of a URL
+pub fn validate_function(function: &OrdFunction) -> Result<(), OrdError> {
+    match function {
+        OrdFunction::SatInfo(SatInfoArgs{ sat }) | OrdFunction::SatInscriptions(SatInscriptionsArgs{ sat, .. }) => sat.parse().map(|_| ()),
+        OrdFunction::AddressInscriptions(AddressInscriptionsArgs{ address, .. }) | OrdFunction::Brc20Balances(Brc20BalancesArgs{ address, .. }) => validate_address(address),
+        OrdFunction::SearchInscriptions(args) => validate_search(args),
+        OrdFunction::Brc20Activity(Brc20ActivityArgs{ address: Some(address), .. }) => validate_address(address),
+        OrdFunction::RuneDetails(RuneDetailsArgs{ rune }) | OrdFunction::RuneHolders(RuneHoldersArgs{ rune, .. }) => validate_rune(rune),
+        OrdFunction::RuneBalances(RuneBalancesArgs{ address, .. }) => validate_address(address),
+        OrdFunction::RuneActivity(RuneActivityArgs{ rune, address, .. }) => {
+            if rune.is_none() && address.is_none() {
+                return Err(OrdError::InvalidArgsError("Either a rune or an address is required".to_string()));
+            }
+            rune.as_ref().map_or(Ok(()), validate_rune)?;
+            address.as_deref().map_or(Ok(()), validate_address)
+        },
+        OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block, .. }) => validate_block(block),
+        _ => Ok(()),
+    }
+}
+
+// The lower bounds of the search ranges shall not exceed the upper ones
+fn validate_search(args: &SearchInscriptionsArgs) -> Result<(), OrdError> {
+    let ranges = [
+        ("number", args.from_number.map(i128::from), args.to_number.map(i128::from)),
+        ("genesis block height", args.from_genesis_block_height.map(i128::from), args.to_genesis_block_height.map(i128::from)),
+        ("genesis timestamp", args.from_genesis_timestamp.map(i128::from), args.to_genesis_timestamp.map(i128::from)),
+    ];
+    for (name, from, to) in ranges {
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err(OrdError::InvalidArgsError(format!("Invalid {} range: {} > {}", name, from, to)));
+            }
+        }
+    }
+    args.genesis_block.as_ref().map_or(Ok(()), validate_block)
+}
+
+// A block hash is made of 32 bytes in hexadecimal
+fn validate_block(block: &BlockId) -> Result<(), OrdError> {
+    match block {
+        BlockId::Hash(hash) if hash.trim().len() != 64 || !hash.trim().chars().all(|c| c.is_ascii_hexdigit()) => {
+            Err(OrdError::InvalidArgsError(format!("Invalid block hash: {}", hash)))
+        },
+        _ => Ok(()),
+    }
+}
+
+// Once the spacers removed, a rune name is made of up to 28 letters
+fn validate_rune(rune: &RuneId) -> Result<(), OrdError> {
+    match rune {
+        RuneId::Name(name) => {
+            let normalized = normalize_rune_name(name);
+            if normalized.is_empty() || normalized.len() > 28 || !normalized.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(OrdError::InvalidArgsError(format!("Invalid rune name: {}", name)));
+            }
+            Ok(())
+        },
+        RuneId::Id { .. } => Ok(()),
+    }
+}
+
+// Bitcoin addresses, whether base58 or bech32, are made of ASCII letters and digits only
+fn validate_address(address: &str) -> Result<(), OrdError> {
+    let address = address.trim();
+    if address.is_empty() || !address.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(OrdError::InvalidArgsError(format!("Invalid address: {}", address)));
+    }
+    Ok(())
+}
+
+// Normalize the function arguments, so that equivalent requests lead to the same arguments
+// (transaction ids, inscription ids and tickers are case insensitive, sat notations are
+// converted to integers)
+pub fn normalize_function(function: OrdFunction) -> OrdFunction {
+    match function {
+        OrdFunction::SatRange(SatRangeArgs{ utxos, exclude_common_ranges }) => OrdFunction::SatRange(SatRangeArgs{
+            utxos: utxos.into_iter().map(|utxo| Utxo{ txid: normalize_id(&utxo.txid), vout: utxo.vout }).collect(),
+            exclude_common_ranges,
+        }),
+        OrdFunction::InscriptionInfo(InscriptionInfoArgs{ inscription_id }) => OrdFunction::InscriptionInfo(InscriptionInfoArgs{
+            inscription_id: normalize_id(&inscription_id),
+        }),
+        OrdFunction::InscriptionTransfers(InscriptionTransfersArgs{ inscription_id, offset, limit }) => OrdFunction::InscriptionTransfers(InscriptionTransfersArgs{
+            inscription_id: normalize_id(&inscription_id),
+            offset,
+            limit,
+        }),
+        OrdFunction::InscriptionContent(InscriptionContentArgs{ inscription_id }) => OrdFunction::InscriptionContent(InscriptionContentArgs{
+            inscription_id: normalize_id(&inscription_id),
+        }),
+        OrdFunction::Brc20Details(Brc20DetailsArgs{ ticker }) => OrdFunction::Brc20Details(Brc20DetailsArgs{
+            ticker: normalize_id(&ticker),
+        }),
+        OrdFunction::Brc20Holders(Brc20HoldersArgs{ ticker, offset, limit }) => OrdFunction::Brc20Holders(Brc20HoldersArgs{
+            ticker: normalize_id(&ticker),
+            offset,
+            limit,
+        }),
+        OrdFunction::Brc20Balances(Brc20BalancesArgs{ address, ticker, offset, limit }) => OrdFunction::Brc20Balances(Brc20BalancesArgs{
+            address: normalize_address(&address),
+            ticker: ticker.map(|ticker| normalize_id(&ticker)),
+            offset,
+            limit,
+        }),
+        OrdFunction::SatInfo(SatInfoArgs{ sat }) => OrdFunction::SatInfo(SatInfoArgs{
+            sat: normalize_sat(sat),
+        }),
+        OrdFunction::SatInscriptions(SatInscriptionsArgs{ sat, offset, limit }) => OrdFunction::SatInscriptions(SatInscriptionsArgs{
+            sat: normalize_sat(sat),
+            offset,
+            limit,
+        }),
+        OrdFunction::AddressInscriptions(AddressInscriptionsArgs{ address, offset, limit }) => OrdFunction::AddressInscriptions(AddressInscriptionsArgs{
+            address: normalize_address(&address),
+            offset,
+            limit,
+        }),
+        // The order of the values of a list filter does not matter
+        OrdFunction::SearchInscriptions(args) => OrdFunction::SearchInscriptions(SearchInscriptionsArgs{
+            mime_type: args.mime_type.map(|mime_types| normalize_list(mime_types.iter().map(|mime_type| normalize_id(mime_type)).collect())),
+            rarity: args.rarity.map(normalize_list),
+            genesis_block: args.genesis_block.map(normalize_block),
+            ..args
+        }),
+        OrdFunction::Brc20Activity(args) => OrdFunction::Brc20Activity(Brc20ActivityArgs{
+            ticker: args.ticker.map(|tickers| normalize_list(tickers.iter().map(|ticker| normalize_id(ticker)).collect())),
+            operation: args.operation.map(normalize_list),
+            address: args.address.map(|address| normalize_address(&address)),
+            ..args
+        }),
+        OrdFunction::Brc20Tokens(Brc20TokensArgs{ ticker_prefix, order_by, offset, limit }) => OrdFunction::Brc20Tokens(Brc20TokensArgs{
+            ticker_prefix: ticker_prefix.map(|prefixes| normalize_list(prefixes.iter().map(|prefix| normalize_id(prefix)).collect())),
+            order_by,
+            offset,
+            limit,
+        }),
+        OrdFunction::RuneDetails(RuneDetailsArgs{ rune }) => OrdFunction::RuneDetails(RuneDetailsArgs{
+            rune: normalize_rune(rune),
+        }),
+        OrdFunction::RuneHolders(RuneHoldersArgs{ rune, offset, limit }) => OrdFunction::RuneHolders(RuneHoldersArgs{
+            rune: normalize_rune(rune),
+            offset,
+            limit,
+        }),
+        OrdFunction::RuneBalances(RuneBalancesArgs{ address, offset, limit }) => OrdFunction::RuneBalances(RuneBalancesArgs{
+            address: normalize_address(&address),
+            offset,
+            limit,
+        }),
+        OrdFunction::RuneActivity(RuneActivityArgs{ rune, address, offset, limit }) => OrdFunction::RuneActivity(RuneActivityArgs{
+            rune: rune.map(normalize_rune),
+            address: address.map(|address| normalize_address(&address)),
+            offset,
+            limit,
+        }),
+        OrdFunction::BlockInscriptionActivity(BlockActivityArgs{ block, offset, limit }) => OrdFunction::BlockInscriptionActivity(BlockActivityArgs{
+            block: normalize_block(block),
+            offset,
+            limit,
+        }),
+    }
+}
+
+fn normalize_sat(sat: SatNotation) -> SatNotation {
+    match sat.parse() {
+        Ok(parsed) => SatNotation::Integer(parsed.n()),
+        Err(_) => sat,
+    }
+}
+
+fn normalize_block(block: BlockId) -> BlockId {
+    match block {
+        BlockId::Hash(hash) => BlockId::Hash(normalize_id(&hash)),
+        height => height,
+    }
+}
+
+fn normalize_rune(rune: RuneId) -> RuneId {
+    match rune {
+        RuneId::Name(name) => RuneId::Name(normalize_rune_name(&name)),
+        id => id,
+    }
+}
+
+// The rune names are case insensitive, and the spacers (• or .) are only meant for display
+pub fn normalize_rune_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .filter(|c| !matches!(c, '•' | '.'))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+// The spacers are kept, but always given as '•'
+pub fn normalize_spaced_rune_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| if c == '.' { '•' } else { c })
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn normalize_id(id: &str) -> String {
+    id.trim().to_lowercase()
+}
+
+fn normalize_list<T: Ord>(mut values: Vec<T>) -> Vec<T> {
+    values.sort();
+    values.dedup();
+    values
+}
+
+// Unlike the base58 addresses, the bech32 ones are case insensitive
+pub fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    let lowercase = address.to_lowercase();
+    if ["bc1", "tb1", "bcrt1"].iter().any(|prefix| lowercase.starts_with(prefix)) {
+        lowercase
+    } else {
+        address.to_string()
+    }
+}
+
+pub fn is_success_status(status: u16) -> bool {
+    (200..300).contains(&status)
+}
+
+// The upstream is either rate limiting or temporarily failing
+pub fn is_transient_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+// Return true if the same request is worth being sent again
+pub fn is_transient_failure(result: &OrdResult) -> bool {
+    match result {
+        Err(OrdError::HttpSendError(HttpSendError{ rejection_code: RejectionCode::SysTransient })) => true,
+        Err(OrdError::HttpStatusError{ status }) => is_transient_status(*status),
+        _ => false,
+    }
+}